@@ -0,0 +1,58 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cli_tables::borders::Width;
+use cli_tables::cells::{Col, DrawCell, Grid, Row, Table, TextCell};
+use cli_tables::config::{CellConfig, UserProperties};
+
+const ROWS: usize = 1000;
+const COLS: usize = 10;
+
+// 10k text cells, every other row with a heavier border
+fn table() -> Table<Col> {
+    let rows = (0..ROWS)
+        .map(|i| {
+            let border = if i % 2 == 0 {
+                Width::Light
+            } else {
+                Width::Heavy
+            };
+            let config = CellConfig::from(UserProperties {
+                border,
+                ..UserProperties::default()
+            });
+            let cells = (0..COLS)
+                .map(|j| {
+                    let cell: Box<dyn DrawCell> =
+                        Box::new(TextCell::new(format!("{}:{}", i, j * i), config));
+                    cell
+                })
+                .collect();
+            let row: Box<dyn DrawCell> = Box::new(Row::new(cells, CellConfig::default()));
+            row
+        })
+        .collect();
+    Table::from(Col::new(rows, CellConfig::default()))
+}
+
+fn render(c: &mut Criterion) {
+    let table = table();
+    let grid = Grid {
+        heights: table.row_heights(),
+        widths: table.column_widths(),
+    };
+    assert_eq!(
+        table.get(&[]).unwrap().draw(grid.slice()).complete(),
+        table.render()
+    );
+
+    let mut group = c.benchmark_group("10k cells");
+    group.sample_size(20);
+    group.bench_function("concatenated views", |b| {
+        b.iter(|| table.get(&[]).unwrap().draw(grid.slice()).complete())
+    });
+    group.bench_function("canvas", |b| b.iter(|| table.render()));
+    group.finish();
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
@@ -1,4 +1,45 @@
 use crate::borders::Width;
+use crate::config::Alignment;
+
+// Chars of a label put on the free points of a line, as (point, text, width).
+// Wide chars need two neighbouring points, zero width ones stay with the char before.
+pub(crate) fn place_label(
+    free: &[usize],
+    text: &str,
+    alignment: Alignment,
+) -> Vec<(usize, String, usize)> {
+    let mut clusters: Vec<(String, usize)> = vec![];
+    let mut buffer = [0; 4];
+    for ch in format!(" {} ", text).chars() {
+        let width = console::measure_text_width(ch.encode_utf8(&mut buffer));
+        match clusters.last_mut() {
+            Some((cluster, _)) if width == 0 => cluster.push(ch),
+            _ => clusters.push((ch.to_string(), width)),
+        }
+    }
+
+    let width: usize = clusters.iter().map(|(_, width)| width).sum();
+    let spare = free.len().saturating_sub(width);
+    let offset = match alignment {
+        Alignment::Default | Alignment::Left => spare.min(1),
+        Alignment::Center => spare / 2,
+        Alignment::Right => spare - spare.min(1),
+    };
+
+    let mut placed = vec![];
+    let mut k = offset;
+    for (cluster, width) in clusters {
+        if width == 2 && k + 1 < free.len() && free[k + 1] != free[k] + 1 {
+            k += 1;
+        }
+        if k + width > free.len() {
+            break;
+        }
+        placed.push((free[k], cluster, width));
+        k += width;
+    }
+    placed
+}
 
 #[derive(Clone, Copy)]
 pub enum Orientation {
@@ -6,6 +47,56 @@ pub enum Orientation {
     Horizontal,
 }
 
+// Pieces of a box drawing char going out of its center
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arms {
+    pub up: Width,
+    pub left: Width,
+    pub down: Width,
+    pub right: Width,
+}
+
+impl Default for Arms {
+    fn default() -> Self {
+        Arms {
+            up: Width::None,
+            left: Width::None,
+            down: Width::None,
+            right: Width::None,
+        }
+    }
+}
+
+impl Arms {
+    pub fn combine(&self, other: &Arms) -> Arms {
+        Arms {
+            up: *self.up.combine(&other.up),
+            left: *self.left.combine(&other.left),
+            down: *self.down.combine(&other.down),
+            right: *self.right.combine(&other.right),
+        }
+    }
+
+    pub fn draw_char(&self) -> char {
+        Width::draw_char(&self.up, &self.left, &self.down, &self.right)
+    }
+
+    pub fn from_char(ch: char) -> Option<Arms> {
+        let (up, left, down, right) = Width::parse_char(ch)?;
+        Some(Arms {
+            up,
+            left,
+            down,
+            right,
+        })
+    }
+
+    // a point of a straight line, nothing joins it from the sides
+    pub fn is_plain(&self) -> bool {
+        self.up == Width::None && self.down == Width::None && self.left == self.right
+    }
+}
+
 #[derive(Clone)]
 struct BorderFragment {
     length: usize,
@@ -104,6 +195,17 @@ impl Border {
         Border::default_right(length, width)
     }
 
+    // a bare line, optionally starting with perpendicular pieces
+    pub fn line(length: usize, width: Width, left_start: Width, right_start: Width) -> Border {
+        Border::new(
+            vec![
+                BorderFragment::new(length - 1, width, left_start, right_start),
+                BorderFragment::new(1, Width::None, Width::None, Width::None),
+            ],
+            length,
+        )
+    }
+
     pub fn add_after(&self, other: &Border) -> Border {
         let mut fragments: Vec<BorderFragment> = self.fragments.clone();
         let last_old = fragments.last_mut().unwrap();
@@ -195,6 +297,48 @@ impl Border {
         Border::new(fragments, self.length)
     }
 
+    // same chars as render_view gives, as arms that can be merged with other borders
+    pub fn arms(&self, orientation: Orientation) -> impl Iterator<Item = Arms> + '_ {
+        self.fragments
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, fragment)| {
+                let prev = match i {
+                    0 => Width::None,
+                    _ => self.fragments[i - 1].width,
+                };
+                let (start, inner) = match orientation {
+                    Orientation::Vertical => (
+                        Arms {
+                            up: prev,
+                            left: fragment.left_start,
+                            down: fragment.width,
+                            right: fragment.right_start,
+                        },
+                        Arms {
+                            up: fragment.width,
+                            down: fragment.width,
+                            ..Arms::default()
+                        },
+                    ),
+                    Orientation::Horizontal => (
+                        Arms {
+                            up: fragment.left_start,
+                            left: prev,
+                            down: fragment.right_start,
+                            right: fragment.width,
+                        },
+                        Arms {
+                            left: fragment.width,
+                            right: fragment.width,
+                            ..Arms::default()
+                        },
+                    ),
+                };
+                std::iter::once(start).chain(std::iter::repeat_n(inner, fragment.length - 1))
+            })
+    }
+
     pub fn render_view(&self, orientation: Orientation) -> String {
         let mut view = String::with_capacity(self.length);
         let mut prev: &Width = &Width::None;
@@ -205,6 +349,43 @@ impl Border {
 
         view
     }
+
+    // Writes text over the plain runs of the border, skipping corners and junctions
+    pub fn render_view_with_text(
+        &self,
+        orientation: Orientation,
+        text: &str,
+        alignment: Alignment,
+    ) -> String {
+        let view: Vec<char> = self.render_view(orientation).chars().collect();
+
+        let mut free: Vec<usize> = Vec::with_capacity(self.length);
+        let mut start = 0;
+        let mut prev: &Width = &Width::None;
+        for fragment in self.fragments.iter() {
+            let plain = fragment.left_start == Width::None
+                && fragment.right_start == Width::None
+                && fragment.width == *prev;
+            if plain && start != 0 {
+                free.push(start);
+            }
+            free.extend((start + 1)..(start + fragment.length));
+            start += fragment.length;
+            prev = &fragment.width;
+        }
+        free.retain(|&i| i + 1 < self.length);
+
+        // the second point of a wide char is left empty
+        let mut view: Vec<String> = view.into_iter().map(String::from).collect();
+        for (i, text, width) in place_label(&free, text, alignment) {
+            view[i] = text;
+            if width == 2 {
+                view[i + 1].clear();
+            }
+        }
+
+        view.into_iter().collect()
+    }
 }
 
 pub struct CellBorder {
@@ -247,6 +428,15 @@ impl CellBorder {
         )
     }
 
+    pub fn top_rule(height_pt: usize, width_pt: usize, width: Width) -> CellBorder {
+        CellBorder::new(
+            Border::line(height_pt, Width::None, Width::None, width),
+            Border::line(height_pt, Width::None, width, Width::None),
+            Border::line(width_pt, width, Width::None, Width::None),
+            Border::line(width_pt, Width::None, Width::None, Width::None),
+        )
+    }
+
     pub fn check_size(&self, height: usize, width: usize) -> bool {
         self.height == height && self.width == width
     }
@@ -308,6 +498,22 @@ impl CellBorder {
         textbox
     }
 
+    // calls put(row, col, arms) for every point of the frame
+    pub fn paint<F: FnMut(usize, usize, Arms)>(&self, mut put: F) {
+        for (i, arms) in self.top.arms(Orientation::Horizontal).enumerate() {
+            put(0, i, arms);
+        }
+        for (i, arms) in self.bottom.arms(Orientation::Horizontal).enumerate() {
+            put(self.height - 1, i, arms);
+        }
+        for (i, arms) in self.left.arms(Orientation::Vertical).enumerate() {
+            put(i, 0, arms);
+        }
+        for (i, arms) in self.right.arms(Orientation::Vertical).enumerate() {
+            put(i, self.width - 1, arms);
+        }
+    }
+
     pub fn render_view_empty(&self) -> Vec<String> {
         self.render_view(&vec![" ".repeat(self.width - 2); self.height - 2])
     }
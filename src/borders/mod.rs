@@ -4,7 +4,8 @@ mod width;
 pub use width::Width;
 
 mod border;
-pub use border::{Border, CellBorder, Orientation};
+pub(crate) use border::place_label;
+pub use border::{Arms, Border, CellBorder, Orientation};
 
 // TODO panic tests
 
@@ -13,6 +14,7 @@ mod tests {
     use super::*;
     use crate::borders::Orientation::*;
     use crate::borders::Width::*;
+    use crate::config::Alignment::*;
     #[test]
     fn border_created() {
         let border = Border::default_top(10, Heavy);
@@ -59,6 +61,36 @@ mod tests {
         assert_eq!("├──██████────┤", final_border.render_view(Horizontal));
     }
 
+    #[test]
+    fn border_text_overlay() {
+        let border = Border::default_top(16, Light);
+        assert_eq!(
+            "┌─ Disk usage ─┐",
+            border.render_view_with_text(Horizontal, "Disk usage", Left)
+        );
+        assert_eq!(
+            "┌───── ab ─────┐",
+            border.render_view_with_text(Horizontal, "ab", Center)
+        );
+        assert_eq!(
+            "┌ Disk usag┐",
+            Border::default_top(12, Light).render_view_with_text(Horizontal, "Disk usage", Right)
+        );
+        assert_eq!(
+            "┌─ 表格 ───┐",
+            Border::default_top(12, Light).render_view_with_text(Horizontal, "表格", Left)
+        );
+    }
+
+    #[test]
+    fn border_text_keeps_junctions() {
+        let border = Border::default_top(6, Light).add_after(&Border::default_top(6, Heavy));
+        assert_eq!(
+            "┌─ ab┲ c ━┓",
+            border.render_view_with_text(Horizontal, "ab c", Left)
+        );
+    }
+
     #[test]
     fn full_border() {
         let cell = CellBorder::atomic(5, 15, Heavy);
@@ -22,6 +22,11 @@ static BOX_DRAWING_MAPPER: [char; 256] = [
 ];
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Width {
     None,
     Light,
@@ -52,6 +57,29 @@ impl Width {
         }
     }
 
+    fn from_index(index: usize) -> Width {
+        match index {
+            0 => Width::None,
+            1 => Width::Light,
+            2 => Width::Heavy,
+            _ => Width::Block,
+        }
+    }
+
+    // widths (top, left, bottom, right) of the simplest point drawn with the char
+    pub fn parse_char(ch: char) -> Option<(Width, Width, Width, Width)> {
+        if ch == '█' {
+            return Some((Width::Block, Width::Block, Width::Block, Width::Block));
+        }
+        let index = BOX_DRAWING_MAPPER.iter().skip(1).position(|&c| c == ch)? + 1;
+        Some((
+            Width::from_index(index / 4 % 4),
+            Width::from_index(index % 4),
+            Width::from_index(index / 16 % 4),
+            Width::from_index(index / 64),
+        ))
+    }
+
     pub fn draw_char(top: &Width, left: &Width, bottom: &Width, right: &Width) -> char {
         BOX_DRAWING_MAPPER
             [((right.index() * 4 + bottom.index()) * 4 + top.index()) * 4 + left.index()]
@@ -0,0 +1,338 @@
+use console::AnsiCodeIterator;
+
+use crate::{
+    borders::{place_label, Arms, CellBorder},
+    config::Alignment,
+};
+
+mod svg;
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy)]
+enum Glyph {
+    // box drawing point, junctions are only turned into chars on output
+    Line(Arms),
+    // char with the index of its style
+    Char(char, u32),
+    // char followed by zero width chars, kept in the clusters of the canvas
+    Cluster(u32, u32),
+    // second column of a wide char
+    Tail,
+}
+
+// Screen sized buffer the cells are painted into. Borders of neighbouring
+// cells land on the same points and are merged there, so every line is
+// assembled once instead of being concatenated on each level of the tree.
+pub struct Canvas {
+    height: usize,
+    width: usize,
+    glyphs: Vec<Glyph>,
+    // ansi prefixes, 0 is the plain style
+    styles: Vec<String>,
+    clusters: Vec<String>,
+}
+
+impl Canvas {
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            height,
+            width,
+            glyphs: vec![Glyph::Line(Arms::default()); height * width],
+            styles: vec![String::new()],
+            clusters: vec![],
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn intern(&mut self, prefix: &str) -> u32 {
+        match self.styles.iter().position(|style| style == prefix) {
+            Some(index) => index as u32,
+            None => {
+                self.styles.push(prefix.to_string());
+                self.styles.len() as u32 - 1
+            }
+        }
+    }
+
+    // lines may be styled, text past the right edge is dropped
+    pub fn text(&mut self, top: usize, left: usize, lines: &[String]) {
+        let mut prefix = String::new();
+        for (y, line) in (top..self.height).zip(lines) {
+            let row = y * self.width;
+            let mut x = left;
+            let mut style = 0;
+            let parts: Box<dyn Iterator<Item = (&str, bool)>> = if line.contains('\x1b') {
+                Box::new(AnsiCodeIterator::new(line))
+            } else {
+                Box::new(std::iter::once((line.as_str(), false)))
+            };
+            for (part, is_ansi) in parts {
+                if is_ansi {
+                    if part == RESET {
+                        prefix.clear();
+                    } else {
+                        prefix.push_str(part);
+                    }
+                    style = self.intern(&prefix);
+                    continue;
+                }
+
+                match self.put(row, left, x, part, style, self.width) {
+                    Some(end) => x = end,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    // unstyled text without escapes on line y, cut at the column end
+    pub fn plain(&mut self, y: usize, x: usize, text: &str, end: usize) {
+        if y < self.height {
+            self.put(y * self.width, x, x, text, 0, end.min(self.width));
+        }
+    }
+
+    // chars of the text from column x on, None once they reach the column end
+    fn put(
+        &mut self,
+        row: usize,
+        left: usize,
+        mut x: usize,
+        text: &str,
+        style: u32,
+        end: usize,
+    ) -> Option<usize> {
+        let mut buffer = [0; 4];
+        for ch in text.chars() {
+            let width = if ch.is_ascii() {
+                1
+            } else {
+                console::measure_text_width(ch.encode_utf8(&mut buffer))
+            };
+            if width == 0 {
+                if x > left {
+                    self.join(row + x - 1, ch);
+                }
+                continue;
+            }
+            if x + width > end {
+                return None;
+            }
+            self.glyphs[row + x] = Glyph::Char(ch, style);
+            for tail in 1..width {
+                self.glyphs[row + x + tail] = Glyph::Tail;
+            }
+            x += width;
+        }
+        Some(x)
+    }
+
+    fn join(&mut self, index: usize, ch: char) {
+        let mut at = index;
+        while at > 0 && matches!(self.glyphs[at], Glyph::Tail) {
+            at -= 1;
+        }
+        match self.glyphs[at] {
+            Glyph::Char(base, style) => {
+                self.clusters.push([base, ch].iter().collect());
+                self.glyphs[at] = Glyph::Cluster(self.clusters.len() as u32 - 1, style);
+            }
+            Glyph::Cluster(cluster, _) => self.clusters[cluster as usize].push(ch),
+            _ => {}
+        }
+    }
+
+    // merges the frame with whatever borders are already there
+    pub fn border(&mut self, top: usize, left: usize, border: &CellBorder) {
+        let width = self.width;
+        let glyphs = &mut self.glyphs;
+        border.paint(|y, x, arms| {
+            let glyph = &mut glyphs[(top + y) * width + left + x];
+            *glyph = match glyph {
+                Glyph::Line(old) => Glyph::Line(old.combine(&arms)),
+                _ => Glyph::Line(arms),
+            };
+        });
+    }
+
+    // copies another canvas over this one, borders are merged like painted ones
+    pub fn blit(&mut self, other: &Canvas, top: usize, left: usize) {
+        let styles: Vec<u32> = other
+            .styles
+            .iter()
+            .map(|style| self.intern(style))
+            .collect();
+        for y in 0..other.height {
+            for x in 0..other.width {
+                let glyph = match other.glyphs[y * other.width + x] {
+                    Glyph::Char(ch, style) => Glyph::Char(ch, styles[style as usize]),
+                    Glyph::Cluster(cluster, style) => {
+                        self.clusters.push(other.clusters[cluster as usize].clone());
+                        Glyph::Cluster(self.clusters.len() as u32 - 1, styles[style as usize])
+                    }
+                    glyph => glyph,
+                };
+                let target = &mut self.glyphs[(top + y) * self.width + left + x];
+                *target = match (*target, glyph) {
+                    (Glyph::Line(old), Glyph::Line(arms)) => Glyph::Line(old.combine(&arms)),
+                    (old, Glyph::Line(arms)) if arms == Arms::default() => old,
+                    (_, glyph) => glyph,
+                };
+            }
+        }
+    }
+
+    // arms of the point, None when there is text over it
+    pub fn line(&self, y: usize, x: usize) -> Option<Arms> {
+        match self.glyphs[y * self.width + x] {
+            Glyph::Line(arms) => Some(arms),
+            _ => None,
+        }
+    }
+
+    // text over a horizontal border, only on the points nothing else joins
+    pub fn label(&mut self, row: usize, text: &str, alignment: Alignment) {
+        let start = row * self.width;
+        let free: Vec<usize> = (1..self.width.saturating_sub(1))
+            .filter(|&x| matches!(self.glyphs[start + x], Glyph::Line(arms) if arms.is_plain()))
+            .collect();
+
+        for (x, text, width) in place_label(&free, text, alignment) {
+            let mut chars = text.chars();
+            self.glyphs[start + x] = Glyph::Char(chars.next().unwrap(), 0);
+            for ch in chars {
+                self.join(start + x, ch);
+            }
+            if width == 2 {
+                self.glyphs[start + x + 1] = Glyph::Tail;
+            }
+        }
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        self.glyphs
+            .chunks(self.width.max(1))
+            .take(self.height)
+            .map(|row| self.render_row(row))
+            .collect()
+    }
+
+    fn render_row(&self, row: &[Glyph]) -> String {
+        let mut line = String::with_capacity(row.len() * 3);
+        let mut current = 0;
+        for glyph in row {
+            let style = match glyph {
+                Glyph::Char(_, style) | Glyph::Cluster(_, style) => *style,
+                Glyph::Line(_) => 0,
+                Glyph::Tail => continue,
+            };
+            if style != current {
+                let (old, new) = (&self.styles[current as usize], &self.styles[style as usize]);
+                match new.strip_prefix(old.as_str()) {
+                    Some(added) if current != 0 => line.push_str(added),
+                    _ => {
+                        if current != 0 {
+                            line.push_str(RESET);
+                        }
+                        line.push_str(new);
+                    }
+                }
+                current = style;
+            }
+            match glyph {
+                Glyph::Line(arms) if *arms == Arms::default() => line.push(' '),
+                Glyph::Line(arms) => line.push(arms.draw_char()),
+                Glyph::Char(ch, _) => line.push(*ch),
+                Glyph::Cluster(cluster, _) => line.push_str(&self.clusters[*cluster as usize]),
+                Glyph::Tail => {}
+            }
+        }
+        if current != 0 {
+            line.push_str(RESET);
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::Width;
+    use crate::cells::{Col, Row, Table, TextCell};
+    use crate::config::{CellConfig, UserProperties};
+
+    fn text(text: &str, border: Width, span_width: usize) -> TextCell {
+        let props = UserProperties {
+            border,
+            span_width,
+            ..UserProperties::default()
+        };
+        TextCell::new(text.to_string(), CellConfig::from(props))
+    }
+
+    // concatenating views, the way tables were rendered before the canvas
+    fn concatenated(table: &Table<Col>) -> Vec<String> {
+        let grid = crate::cells::Grid {
+            heights: table.row_heights(),
+            widths: table.column_widths(),
+        };
+        table.get(&[]).unwrap().draw(grid.slice()).complete()
+    }
+
+    #[test]
+    fn same_as_concatenation() {
+        let top = Row::new(
+            vec![
+                Box::new(text("a", Width::Light, 1)),
+                Box::new(text("wide\ncell", Width::Heavy, 1)),
+                Box::new(text("ｗｉｄｅ", Width::Light, 1)),
+            ],
+            CellConfig::default(),
+        );
+        let bottom = Row::new(
+            vec![
+                Box::new(text("spanning", Width::Block, 2)),
+                Box::new(text("c", Width::None, 1)),
+            ],
+            CellConfig::from(UserProperties {
+                border: Width::Heavy,
+                ..UserProperties::default()
+            }),
+        );
+        let table = Table::from(Col::new(
+            vec![Box::new(top), Box::new(bottom)],
+            CellConfig::default(),
+        ));
+
+        assert_eq!(concatenated(&table), table.render());
+    }
+
+    #[test]
+    fn styles_and_labels() {
+        let mut canvas = Canvas::new(3, 8);
+        canvas.border(0, 0, &CellBorder::atomic(3, 8, Width::Light));
+        canvas.text(1, 1, &["\x1b[31mred\x1b[1m!\x1b[0m  ".to_string()]);
+        canvas.label(0, "t", Alignment::Center);
+        assert_eq!(
+            vec![
+                "┌─ t ──┐".to_string(),
+                "│\x1b[31mred\x1b[1m!\x1b[0m  │".to_string(),
+                "└──────┘".to_string(),
+            ],
+            canvas.render()
+        );
+
+        let mut canvas = Canvas::new(2, 8);
+        canvas.border(0, 0, &CellBorder::atomic(2, 8, Width::Light));
+        canvas.label(0, "表", Alignment::Left);
+        assert_eq!("┌─ 表 ─┐", canvas.render()[0]);
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt::Write;
+
+use super::{Canvas, Glyph};
+use crate::borders::{Arms, Width};
+
+// every char takes a fixed box, so the picture does not depend on the font metrics
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 20;
+const FONT_SIZE: usize = 16;
+const FOREGROUND: &str = "#000000";
+const BACKGROUND: &str = "#ffffff";
+
+// xterm defaults for the 16 basic colours
+const BASIC: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn color(index: u8) -> String {
+    let (r, g, b) = match index {
+        0..=15 => BASIC[index as usize],
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + 40 * n };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let grey = 8 + 10 * (index - 232);
+            (grey, grey, grey)
+        }
+    };
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// what an ansi prefix collected by the canvas turns into
+#[derive(Debug, Default, PartialEq)]
+struct Attributes {
+    fg: Option<String>,
+    bg: Option<String>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underlined: bool,
+}
+
+impl Attributes {
+    fn parse(prefix: &str) -> Self {
+        let mut attributes = Self::default();
+        let codes = prefix
+            .split('\x1b')
+            .filter_map(|code| code.strip_prefix('[')?.strip_suffix('m'));
+        for code in codes {
+            let mut params = code.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+            while let Some(param) = params.next() {
+                match param {
+                    0 => attributes = Self::default(),
+                    1 => attributes.bold = true,
+                    2 => attributes.dim = true,
+                    3 => attributes.italic = true,
+                    4 => attributes.underlined = true,
+                    30..=37 => attributes.fg = Some(color(param - 30)),
+                    40..=47 => attributes.bg = Some(color(param - 40)),
+                    90..=97 => attributes.fg = Some(color(param - 90 + 8)),
+                    100..=107 => attributes.bg = Some(color(param - 100 + 8)),
+                    39 => attributes.fg = None,
+                    49 => attributes.bg = None,
+                    38 | 48 => {
+                        let value = match params.next() {
+                            Some(5) => params.next().map(color),
+                            Some(2) => {
+                                let mut channel = || params.next().unwrap_or(0);
+                                let (r, g, b) = (channel(), channel(), channel());
+                                Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+                            }
+                            _ => None,
+                        };
+                        if param == 38 {
+                            attributes.fg = value;
+                        } else {
+                            attributes.bg = value;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        attributes
+    }
+
+    fn svg(&self) -> String {
+        let mut attrs = String::new();
+        if let Some(fg) = &self.fg {
+            write!(attrs, " fill=\"{}\"", fg).unwrap();
+        }
+        if self.bold {
+            attrs.push_str(" font-weight=\"bold\"");
+        }
+        if self.dim {
+            attrs.push_str(" fill-opacity=\"0.6\"");
+        }
+        if self.italic {
+            attrs.push_str(" font-style=\"italic\"");
+        }
+        if self.underlined {
+            attrs.push_str(" text-decoration=\"underline\"");
+        }
+        attrs
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn stroke(width: Width) -> usize {
+    match width {
+        Width::Heavy => 3,
+        _ => 1,
+    }
+}
+
+fn is_block(arms: &Arms) -> bool {
+    [arms.up, arms.left, arms.down, arms.right].contains(&Width::Block)
+}
+
+// merges equal neighbours, calling out(start, length, value) for every run
+fn runs<T: PartialEq + Copy, F: FnMut(usize, usize, T)>(
+    values: impl Iterator<Item = T>,
+    mut out: F,
+) {
+    let mut current: Option<(usize, usize, T)> = None;
+    for (i, value) in values.enumerate() {
+        current = match current {
+            Some((start, length, run)) if run == value => Some((start, length + 1, run)),
+            Some((start, length, run)) => {
+                out(start, length, run);
+                Some((i, 1, value))
+            }
+            None => Some((i, 1, value)),
+        };
+    }
+    if let Some((start, length, run)) = current {
+        out(start, length, run);
+    }
+}
+
+// text of one style on one line
+struct Span {
+    x: usize,
+    columns: usize,
+    text: String,
+    style: u32,
+}
+
+impl Canvas {
+    fn spans(&self, y: usize) -> Vec<Span> {
+        let mut spans: Vec<Span> = vec![];
+        let row = &self.glyphs[y * self.width..(y + 1) * self.width];
+        let mut open = false;
+        for (x, glyph) in row.iter().enumerate() {
+            let (text, style) = match glyph {
+                Glyph::Char(ch, style) => (ch.to_string(), *style),
+                Glyph::Cluster(cluster, style) => {
+                    (self.clusters[*cluster as usize].clone(), *style)
+                }
+                Glyph::Tail => {
+                    if let Some(span) = spans.last_mut().filter(|_| open) {
+                        span.columns += 1;
+                    }
+                    continue;
+                }
+                Glyph::Line(_) => {
+                    open = false;
+                    continue;
+                }
+            };
+            match spans.last_mut() {
+                Some(span) if open && span.style == style => {
+                    span.columns += 1;
+                    span.text.push_str(&text);
+                }
+                _ => spans.push(Span {
+                    x,
+                    columns: 1,
+                    text,
+                    style,
+                }),
+            }
+            open = true;
+        }
+        spans
+    }
+
+    // Monospaced picture of the canvas. Box drawing chars become strokes
+    // so the borders join up whatever font the viewer picks for the text.
+    pub fn svg(&self) -> String {
+        let (width, height) = (self.width * CELL_WIDTH, self.height * CELL_HEIGHT);
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" \
+             font-family=\"monospace\" font-size=\"{2}\" xml:space=\"preserve\">",
+            width, height, FONT_SIZE
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            width, height, BACKGROUND
+        )
+        .unwrap();
+
+        let attributes: Vec<Attributes> =
+            self.styles.iter().map(|s| Attributes::parse(s)).collect();
+        let spans: Vec<Vec<Span>> = (0..self.height).map(|y| self.spans(y)).collect();
+
+        for (y, line) in spans.iter().enumerate() {
+            for span in line {
+                if let Some(bg) = &attributes[span.style as usize].bg {
+                    writeln!(
+                        svg,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        span.x * CELL_WIDTH,
+                        y * CELL_HEIGHT,
+                        span.columns * CELL_WIDTH,
+                        CELL_HEIGHT,
+                        bg
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        // lines are cut into half cells, so the arms of a junction meet in the middle
+        writeln!(
+            svg,
+            "<g stroke=\"{}\" stroke-linecap=\"square\" shape-rendering=\"crispEdges\">",
+            FOREGROUND
+        )
+        .unwrap();
+        let half = (CELL_WIDTH / 2, CELL_HEIGHT / 2);
+        for y in 0..self.height {
+            let halves = (0..self.width).flat_map(|x| {
+                let arms = self
+                    .line(y, x)
+                    .filter(|arms| !is_block(arms))
+                    .unwrap_or_default();
+                vec![arms.left, arms.right]
+            });
+            let middle = y * CELL_HEIGHT + half.1;
+            runs(halves, |start, length, width| {
+                if width != Width::None {
+                    writeln!(
+                        svg,
+                        "<line x1=\"{}\" y1=\"{3}\" x2=\"{}\" y2=\"{3}\" stroke-width=\"{}\"/>",
+                        start * half.0,
+                        (start + length) * half.0,
+                        stroke(width),
+                        middle
+                    )
+                    .unwrap();
+                }
+            });
+        }
+        for x in 0..self.width {
+            let halves = (0..self.height).flat_map(|y| {
+                let arms = self
+                    .line(y, x)
+                    .filter(|arms| !is_block(arms))
+                    .unwrap_or_default();
+                vec![arms.up, arms.down]
+            });
+            let middle = x * CELL_WIDTH + half.0;
+            runs(halves, |start, length, width| {
+                if width != Width::None {
+                    writeln!(
+                        svg,
+                        "<line x1=\"{3}\" y1=\"{}\" x2=\"{3}\" y2=\"{}\" stroke-width=\"{}\"/>",
+                        start * half.1,
+                        (start + length) * half.1,
+                        stroke(width),
+                        middle
+                    )
+                    .unwrap();
+                }
+            });
+        }
+        svg.push_str("</g>\n");
+
+        for y in 0..self.height {
+            let blocks = (0..self.width).map(
+                |x| matches!(self.glyphs[y * self.width + x], Glyph::Line(arms) if is_block(&arms)),
+            );
+            runs(blocks, |start, length, block| {
+                if block {
+                    writeln!(
+                        svg,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        start * CELL_WIDTH,
+                        y * CELL_HEIGHT,
+                        length * CELL_WIDTH,
+                        CELL_HEIGHT,
+                        FOREGROUND
+                    )
+                    .unwrap();
+                }
+            });
+        }
+
+        writeln!(
+            svg,
+            "<g fill=\"{}\" dominant-baseline=\"central\">",
+            FOREGROUND
+        )
+        .unwrap();
+        for (y, line) in spans.iter().enumerate() {
+            for span in line.iter().filter(|span| !span.text.trim().is_empty()) {
+                writeln!(
+                    svg,
+                    "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\"{}>{}</text>",
+                    span.x * CELL_WIDTH,
+                    y * CELL_HEIGHT + half.1,
+                    span.columns * CELL_WIDTH,
+                    attributes[span.style as usize].svg(),
+                    escape(&span.text)
+                )
+                .unwrap();
+            }
+        }
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::CellBorder;
+
+    #[test]
+    fn ansi_attributes() {
+        assert_eq!(
+            Attributes {
+                fg: Some("#cd0000".to_string()),
+                bg: Some("#5f87af".to_string()),
+                bold: true,
+                ..Attributes::default()
+            },
+            Attributes::parse("\x1b[31m\x1b[48;5;67m\x1b[1m")
+        );
+        assert_eq!(
+            Some("#0a141e".to_string()),
+            Attributes::parse("\x1b[4m\x1b[0m\x1b[38;2;10;20;30m").fg
+        );
+    }
+
+    #[test]
+    fn strokes_and_text() {
+        let mut canvas = Canvas::new(3, 5);
+        canvas.border(0, 0, &CellBorder::atomic(3, 5, Width::Heavy));
+        canvas.text(1, 1, &["\x1b[32ma<b\x1b[0m".to_string()]);
+        let svg = canvas.svg();
+
+        // one stroke per side, the corners are joined by the square caps
+        assert_eq!(4, svg.matches("<line").count());
+        assert!(svg.contains("<line x1=\"5\" y1=\"10\" x2=\"45\" y2=\"10\" stroke-width=\"3\"/>"));
+        assert!(svg.contains(
+            "<text x=\"10\" y=\"30\" textLength=\"30\" lengthAdjust=\"spacingAndGlyphs\" \
+             fill=\"#00cd00\">a&lt;b</text>"
+        ));
+    }
+}
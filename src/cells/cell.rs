@@ -1,8 +1,10 @@
 use crate::config::CellConfig;
 
-use super::{CellView, GridSlice, GridSliceMut};
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError};
+use super::{Canvas, CellView, GridSlice, GridSliceMut};
 
-fn increase_to_size(slice: &mut [usize], size: usize) {
+pub(super) fn increase_to_size(slice: &mut [usize], size: usize) {
     let sum: usize = slice.iter().sum();
     if sum >= size {
         return;
@@ -20,13 +22,66 @@ fn increase_to_size(slice: &mut [usize], size: usize) {
     debug_assert_eq!(slice.iter().sum::<usize>(), size)
 }
 
+// Layout protocol shared by the built-in cells. Custom cells are easier
+// and safer to write with the Content trait and the ContentCell adapter.
 pub trait Cell {
     fn get_config(&self) -> &CellConfig;
     fn get_config_mut(&mut self) -> &mut CellConfig;
+    // config as it was given by the user, before any fixups
+    fn get_base(&self) -> &CellConfig;
+    fn get_base_mut(&mut self) -> &mut CellConfig;
     fn debug_str(&self) -> String;
+    // recomputes config from base and content, dropping the fixups
+    fn reset_config(&mut self);
     fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize);
     fn fixup_grid(&self, grid: GridSliceMut);
 
+    fn children(&self) -> &[Box<dyn DrawCell>] {
+        &[]
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn DrawCell>>> {
+        None
+    }
+
+    // Breaks the cell into leaves and borders that other threads may paint, in
+    // the order paint goes through them. False when a cell on the way is not Sync.
+    #[cfg(feature = "rayon")]
+    #[doc(hidden)]
+    fn parts<'a>(
+        &'a self,
+        _grid: GridSlice<'a>,
+        _top: usize,
+        _left: usize,
+        _parts: &mut Vec<parts::Part<'a>>,
+    ) -> bool {
+        false
+    }
+
+    // (row, col) of every child in grid units, relative to this cell
+    fn child_offsets(&self) -> Vec<(usize, usize)> {
+        vec![]
+    }
+
+    fn text(&self) -> Option<&str> {
+        None
+    }
+
+    // tags for stylesheet selectors, inherited by all the cells below
+    fn classes(&self) -> &[String] {
+        &[]
+    }
+
+    fn set_text(&mut self, _text: String) -> bool {
+        false
+    }
+
+    // cells that cannot be serialised name themselves in the error
+    #[cfg(feature = "serde")]
+    fn describe(&self, _rect: Rect) -> Result<Node, SnapshotError> {
+        Err(SnapshotError::new(std::any::type_name::<Self>()))
+    }
+
     fn fixup_config_default(&mut self, row_ratio: usize, col_ratio: usize) {
         let config = self.get_config_mut();
         config.span_height *= row_ratio;
@@ -42,12 +97,120 @@ pub trait Cell {
 
 pub trait Draw {
     fn draw(&self, grid: GridSlice) -> CellView;
+
+    // same picture as draw, painted with the top left corner at (top, left);
+    // containers override it to paint their children in place
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        self.draw(grid).paint(canvas, top, left)
+    }
 }
 
 // for container elements
 pub trait DrawCell: Draw + Cell {}
 impl<T: Draw + Cell> DrawCell for T {}
 
+#[cfg(feature = "rayon")]
+pub(crate) mod parts {
+    use super::{Canvas, DrawCell, GridSlice};
+    use crate::borders::CellBorder;
+
+    // the compiler checks that every part may be sent to another thread
+    pub enum Part<'a> {
+        Cell(&'a (dyn DrawCell + Sync), GridSlice<'a>, usize, usize),
+        Border(usize, usize, CellBorder),
+    }
+
+    // the only part of a leaf is the leaf itself
+    pub fn leaf<'a, T: DrawCell + Sync>(
+        cell: &'a T,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        parts.push(Part::Cell(cell, grid, top, left));
+        true
+    }
+
+    pub fn paint(parts: Vec<Part>, canvas: &mut Canvas) {
+        for part in parts {
+            match part {
+                Part::Cell(cell, grid, top, left) => cell.paint(grid, canvas, top, left),
+                Part::Border(top, left, border) => canvas.border(top, left, &border),
+            }
+        }
+    }
+}
+
+// lets a table hold a tree whose root type is only known at runtime
+impl Cell for Box<dyn DrawCell> {
+    fn get_config(&self) -> &CellConfig {
+        self.as_ref().get_config()
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        self.as_mut().get_config_mut()
+    }
+    fn get_base(&self) -> &CellConfig {
+        self.as_ref().get_base()
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        self.as_mut().get_base_mut()
+    }
+    fn debug_str(&self) -> String {
+        self.as_ref().debug_str()
+    }
+    fn reset_config(&mut self) {
+        self.as_mut().reset_config()
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.as_mut().fixup_config(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.as_ref().fixup_grid(grid)
+    }
+    fn children(&self) -> &[Box<dyn DrawCell>] {
+        self.as_ref().children()
+    }
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn DrawCell>>> {
+        self.as_mut().children_mut()
+    }
+    fn child_offsets(&self) -> Vec<(usize, usize)> {
+        self.as_ref().child_offsets()
+    }
+    fn text(&self) -> Option<&str> {
+        self.as_ref().text()
+    }
+    fn set_text(&mut self, text: String) -> bool {
+        self.as_mut().set_text(text)
+    }
+    fn classes(&self) -> &[String] {
+        self.as_ref().classes()
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        self.as_ref().describe(rect)
+    }
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<parts::Part<'a>>,
+    ) -> bool {
+        self.as_ref().parts(grid, top, left, parts)
+    }
+}
+
+impl Draw for Box<dyn DrawCell> {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        self.as_ref().draw(grid)
+    }
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        self.as_ref().paint(grid, canvas, top, left)
+    }
+}
+
 impl std::fmt::Debug for dyn DrawCell {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         write!(fmt, "{}", self.debug_str())
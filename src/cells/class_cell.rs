@@ -0,0 +1,99 @@
+use crate::config::CellConfig;
+
+#[cfg(feature = "rayon")]
+use super::cell::parts::Part;
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError};
+use super::{Canvas, Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut};
+
+// Tags the wrapped cell and everything inside it with stylesheet classes.
+// Otherwise the wrapper is transparent, like RuleCell.
+#[derive(Debug)]
+pub struct ClassCell {
+    cell: Box<dyn DrawCell>,
+    classes: Vec<String>,
+}
+
+impl ClassCell {
+    pub fn new<S: ToString>(cell: Box<dyn DrawCell>, classes: &[S]) -> Self {
+        // the classes of a wrapped wrapper would be hidden behind this one
+        let mut own: Vec<String> = classes.iter().map(|c| c.to_string()).collect();
+        for class in cell.classes() {
+            if !own.contains(class) {
+                own.push(class.clone());
+            }
+        }
+        Self { cell, classes: own }
+    }
+}
+
+impl Cell for ClassCell {
+    fn get_config(&self) -> &CellConfig {
+        self.cell.get_config()
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        self.cell.get_config_mut()
+    }
+    fn get_base(&self) -> &CellConfig {
+        self.cell.get_base()
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        self.cell.get_base_mut()
+    }
+    fn debug_str(&self) -> String {
+        format!("{:?}", self)
+    }
+    fn reset_config(&mut self) {
+        self.cell.reset_config()
+    }
+    fn children(&self) -> &[Box<dyn DrawCell>] {
+        self.cell.children()
+    }
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        self.cell.parts(grid, top, left, parts)
+    }
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn DrawCell>>> {
+        self.cell.children_mut()
+    }
+    fn child_offsets(&self) -> Vec<(usize, usize)> {
+        self.cell.child_offsets()
+    }
+    fn text(&self) -> Option<&str> {
+        self.cell.text()
+    }
+    fn set_text(&mut self, text: String) -> bool {
+        self.cell.set_text(text)
+    }
+    fn classes(&self) -> &[String] {
+        &self.classes
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Class {
+            classes: self.classes.clone(),
+            cell: Box::new(self.cell.describe(rect)?),
+        })
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.cell.fixup_config(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.cell.fixup_grid(grid)
+    }
+}
+
+impl Draw for ClassCell {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        self.cell.draw(grid)
+    }
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        self.cell.paint(grid, canvas, top, left)
+    }
+}
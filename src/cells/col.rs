@@ -3,30 +3,28 @@ use crate::{
     config::{Bound, CellConfig, Vertical},
 };
 
-use super::{Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut};
+#[cfg(feature = "rayon")]
+use super::cell::parts::{self, Part};
+#[cfg(feature = "serde")]
+use super::snapshot::{self, Node, Rect, SnapshotError};
+use super::{Canvas, Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut};
 
 #[derive(Debug)]
 pub struct Col {
     rows: Vec<Box<dyn DrawCell>>,
+    base: CellConfig,
     config: CellConfig,
 }
 
 impl Col {
     pub fn new(rows: Vec<Box<dyn DrawCell>>, config: CellConfig) -> Self {
-        let child = rows
-            .iter()
-            .map(|x| *x.get_config())
-            .collect::<Vertical<_>>()
-            .0;
-        Self {
+        let mut cell = Self {
             rows,
-            config: CellConfig {
-                bounds: config.bounds + child.bounds,
-                span_height: child.span_height,
-                span_width: child.span_width,
-                ..config
-            },
-        }
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
     }
 }
 
@@ -37,10 +35,81 @@ impl Cell for Col {
     fn get_config_mut(&mut self) -> &mut CellConfig {
         &mut self.config
     }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
     fn debug_str(&self) -> String {
         format!("{:?}", self)
     }
 
+    fn reset_config(&mut self) {
+        for cell in self.rows.iter_mut() {
+            cell.reset_config();
+        }
+
+        let child = self
+            .rows
+            .iter()
+            .map(|x| *x.get_config())
+            .collect::<Vertical<_>>()
+            .0;
+        self.config = CellConfig {
+            bounds: self.base.bounds + child.bounds,
+            span_height: child.span_height,
+            span_width: child.span_width,
+            ..self.base
+        };
+    }
+
+    fn children(&self) -> &[Box<dyn DrawCell>] {
+        &self.rows
+    }
+
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        let mut sync = true;
+        let outer = self.place(grid, top, left, |cell, slice, y, x| {
+            sync = sync && cell.parts(slice, y, x, parts)
+        });
+        parts.push(Part::Border(top, left, outer));
+        sync
+    }
+
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Col {
+            base: self.base,
+            config: self.config,
+            rect,
+            children: snapshot::describe_children(self, rect)?,
+        })
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn DrawCell>>> {
+        Some(&mut self.rows)
+    }
+
+    fn child_offsets(&self) -> Vec<(usize, usize)> {
+        let mut start = 0;
+        self.rows
+            .iter()
+            .map(|cell| {
+                let offset = (start, 0);
+                start += cell.get_config().span_height;
+                offset
+            })
+            .collect()
+    }
+
     fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
         self.fixup_config_default(row_ratio, col_ratio);
 
@@ -63,21 +132,149 @@ impl Cell for Col {
     }
 }
 
-impl Draw for Col {
-    fn draw(&self, grid: GridSlice) -> CellView {
+impl Col {
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn row_height(&self, grid: &GridSlice, index: usize) -> usize {
+        let start: usize = self.rows[..index]
+            .iter()
+            .map(|row| row.get_config().span_height)
+            .sum();
+        let len = self.rows[index].get_config().span_height;
+        grid.slice(start..(start + len), ..).get_bound().pt_height
+    }
+
+    // first grid row of every child
+    fn starts(&self) -> Vec<usize> {
+        let mut starts: Vec<usize> = Vec::with_capacity(self.rows.len());
+        let mut start = 0;
+        for row in self.rows.iter() {
+            starts.push(start);
+            start += row.get_config().span_height;
+        }
+        starts
+    }
+
+    // hands every row its slice and corner, the outer border is left to the caller
+    fn place<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        mut child: impl FnMut(&'a dyn DrawCell, GridSlice<'a>, usize, usize),
+    ) -> CellBorder {
         let Bound {
             pt_height,
             pt_width,
         } = grid.get_bound();
 
-        let mut textbox: Vec<String> = Vec::with_capacity(pt_height);
-        let mut total_border: Option<CellBorder> = None;
-
         let mut start = 0;
+        let mut y = top;
         for row in self.rows.iter() {
             let len = row.get_config().span_height;
-            let (row_textbox, border) = row.draw(grid.slice(start..(start + len), ..)).unwrap();
+            let slice = grid.slice(start..(start + len), ..);
+            // neighbours share the border line
+            let height: usize = slice.heights.iter().sum();
+            child(row.as_ref(), slice, y, left);
             start += len;
+            y += height;
+        }
+
+        CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border)
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn paint_parallel(
+        &self,
+        _grid: GridSlice,
+        _canvas: &mut Canvas,
+        _top: usize,
+        _left: usize,
+    ) -> bool {
+        false
+    }
+
+    // Every row is broken into parts at the corner of a canvas of its own,
+    // the canvases are merged in order. False when some row is not Sync.
+    #[cfg(feature = "rayon")]
+    fn paint_parallel(
+        &self,
+        grid: GridSlice,
+        canvas: &mut Canvas,
+        top: usize,
+        left: usize,
+    ) -> bool {
+        use rayon::prelude::*;
+
+        let mut rows = vec![];
+        let mut sync = true;
+        let outer = self.place(grid, top, left, |row, slice, y, _| {
+            let bound = slice.get_bound();
+            let mut row_parts = vec![];
+            sync = sync && row.parts(slice, 0, 0, &mut row_parts);
+            rows.push((row_parts, y, bound));
+        });
+        if !sync {
+            return false;
+        }
+
+        let painted: Vec<(Canvas, usize)> = rows
+            .into_par_iter()
+            .map(|(row_parts, y, bound)| {
+                let mut row = Canvas::new(bound.pt_height + 2, bound.pt_width + 2);
+                parts::paint(row_parts, &mut row);
+                (row, y)
+            })
+            .collect();
+        for (row, y) in painted {
+            canvas.blit(&row, y, left);
+        }
+        canvas.border(top, left, &outer);
+        true
+    }
+
+    // paints only the given rows one under another, closing the outer border around them
+    pub fn paint_rows(
+        &self,
+        grid: GridSlice,
+        rows: &[usize],
+        canvas: &mut Canvas,
+        top: usize,
+        left: usize,
+    ) {
+        let starts = self.starts();
+        let mut y = top;
+        for &index in rows {
+            let len = self.rows[index].get_config().span_height;
+            let slice = grid.slice(starts[index]..(starts[index] + len), ..);
+            let height: usize = slice.heights.iter().sum();
+            self.rows[index].paint(slice, canvas, y, left);
+            y += height;
+        }
+
+        let width = grid.get_bound().pt_width + 2;
+        let outer = CellBorder::atomic(y - top + 1, width, self.config.border);
+        canvas.border(top, left, &outer);
+    }
+
+    // views are consumed lazily, so the rows that do not fit are never drawn
+    fn stitch<I: IntoIterator<Item = CellView>>(&self, grid: &GridSlice, views: I) -> CellView {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+
+        let mut textbox: Vec<String> = Vec::with_capacity(pt_height);
+        let mut total_border: Option<CellBorder> = None;
+
+        for view in views {
+            let (row_textbox, border) = view.unwrap();
 
             if !textbox.is_empty() && textbox.len() + 1 + row_textbox.len() > pt_height {
                 break;
@@ -103,11 +300,35 @@ impl Draw for Col {
             textbox.extend(row_textbox);
         }
 
-        let outer = CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border);
+        let outer = CellBorder::atomic(textbox.len() + 2, pt_width + 2, self.config.border);
         CellView::new(textbox, total_border.unwrap().combine(&outer))
     }
 }
 
+impl Draw for Col {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        let mut start = 0;
+        let views = self.rows.iter().map(|row| {
+            let len = row.get_config().span_height;
+            let view = row.draw(grid.slice(start..(start + len), ..));
+            start += len;
+            view
+        });
+        self.stitch(&grid, views)
+    }
+
+    // with the rayon feature rows made of Sync cells are painted in parallel
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        if self.paint_parallel(grid.slice(.., ..), canvas, top, left) {
+            return;
+        }
+        let outer = self.place(grid, top, left, |row, slice, y, x| {
+            row.paint(slice, canvas, y, x)
+        });
+        canvas.border(top, left, &outer);
+    }
+}
+
 #[macro_export]
 macro_rules! col {
     ({$($i:ident=$e:expr),* $(,)?}, $($x:expr),* $(,)?) => {{
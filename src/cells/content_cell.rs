@@ -0,0 +1,191 @@
+use crate::{
+    borders::CellBorder,
+    config::{Bound, CellBounds, CellConfig},
+};
+
+use super::text_cell::{box_align, pad};
+use super::{Cell, CellView, Draw, GridSlice, GridSliceMut};
+
+/// Content of a user-defined cell.
+///
+/// This is the stable way to add new kinds of cells. Borders, padding,
+/// alignment, styles and grid bookkeeping are handled by [`ContentCell`],
+/// the content only has to say how much room it wants and fill the room it gets.
+///
+/// ```
+/// use cli_tables::borders::Width;
+/// use cli_tables::cells::{Content, ContentCell, Table};
+/// use cli_tables::config::{CellConfig, UserProperties};
+///
+/// struct Stars(usize);
+///
+/// impl Content for Stars {
+///     fn measure(&self) -> (usize, usize) {
+///         (1, self.0)
+///     }
+///
+///     fn render(&self, _height: usize, _width: usize) -> Vec<String> {
+///         vec!["*".repeat(self.0)]
+///     }
+/// }
+///
+/// let config = CellConfig::from(UserProperties {
+///     border: Width::Light,
+///     ..UserProperties::default()
+/// });
+/// let table = Table::from(ContentCell::new(Stars(3), config));
+/// assert_eq!(vec!["┌───┐", "│***│", "└───┘"], table.render());
+/// ```
+pub trait Content {
+    /// Preferred size as `(height, width)` in characters, without padding and borders.
+    /// The cell may end up larger when its row or column is stretched by other cells.
+    fn measure(&self) -> (usize, usize);
+
+    /// Lines for a box of `height` by `width` characters, padding excluded.
+    ///
+    /// The result does not have to fit exactly: missing lines are filled with blanks,
+    /// extra lines are dropped and every line is aligned and cut to `width`.
+    fn render(&self, height: usize, width: usize) -> Vec<String>;
+
+    /// Text for the table addressing API, see [`Table::text`](super::Table::text).
+    fn text(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Adapter that turns any [`Content`] into a cell which can be put into rows, columns and tables.
+#[derive(Debug)]
+pub struct ContentCell<T: Content> {
+    content: T,
+    base: CellConfig,
+    config: CellConfig,
+}
+
+impl<T: Content> ContentCell<T> {
+    pub fn new(content: T, config: CellConfig) -> Self {
+        let mut cell = Self {
+            content,
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
+    }
+
+    pub fn content(&self) -> &T {
+        &self.content
+    }
+
+    /// Changes the content in place. A table holding the cell has to be updated
+    /// through [`Table::update`](super::Table::update) to pick up the new size.
+    pub fn content_mut(&mut self) -> &mut T {
+        &mut self.content
+    }
+}
+
+impl<T: Content> Cell for ContentCell<T> {
+    fn get_config(&self) -> &CellConfig {
+        &self.config
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        &mut self.config
+    }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
+    fn debug_str(&self) -> String {
+        format!(
+            "ContentCell {{ base: {:?}, config: {:?} }}",
+            self.base, self.config
+        )
+    }
+    fn reset_config(&mut self) {
+        let (height, width) = self.content.measure();
+        self.config = CellConfig {
+            bounds: self.base.bounds + CellBounds::from_box(height, width, self.base.padding),
+            ..self.base
+        };
+    }
+    fn text(&self) -> Option<&str> {
+        self.content.text()
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.fixup_config_default(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.fixup_grid_default(grid)
+    }
+}
+
+impl<T: Content> Draw for ContentCell<T> {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+        let padding = self.config.padding;
+        let width = pt_width.saturating_sub(2 * padding);
+        let alignment = self.config.alignment.console(true);
+
+        // whatever the content returns, the view gets exactly pt_height lines of pt_width
+        let lines = self
+            .content
+            .render(pt_height, width)
+            .into_iter()
+            .take(pt_height)
+            .map(|line| {
+                pad(
+                    &console::pad_str(&line, width, alignment, Some("")),
+                    padding,
+                )
+            })
+            .collect();
+        let textbox = box_align(lines, pt_height, pt_width)
+            .iter()
+            .map(|line| self.config.style.apply(line))
+            .collect();
+
+        CellView::new(
+            textbox,
+            CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::Width;
+    use crate::cells::Table;
+    use crate::config::UserProperties;
+
+    // deliberately sloppy: wrong line count and widths
+    struct Sloppy;
+
+    impl Content for Sloppy {
+        fn measure(&self) -> (usize, usize) {
+            (2, 3)
+        }
+
+        fn render(&self, _height: usize, _width: usize) -> Vec<String> {
+            vec!["too wide".to_string(), "".to_string(), "extra".to_string()]
+        }
+    }
+
+    #[test]
+    fn forgiving_render() {
+        let config = CellConfig::from(UserProperties {
+            border: Width::Light,
+            pt_height: 3,
+            ..UserProperties::default()
+        });
+        let table = Table::from(ContentCell::new(Sloppy, config));
+        assert_eq!(
+            vec!["┌───┐", "│too│", "│   │", "│ext│", "└───┘"],
+            table.render()
+        );
+    }
+}
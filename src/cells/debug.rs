@@ -0,0 +1,263 @@
+use crate::borders::Arms;
+
+use super::cell::increase_to_size;
+use super::path::Placed;
+use super::{Canvas, DrawCell, Table};
+
+const GRID_VERTICAL: char = '╎';
+const GRID_HORIZONTAL: char = '╌';
+const GRID_CROSS: char = '┼';
+
+// offset of every grid line, the last one is the closing border
+pub(super) fn lines(units: &[usize]) -> Vec<usize> {
+    let mut offset = 0;
+    let mut lines = vec![0];
+    for unit in units {
+        offset += unit;
+        lines.push(offset);
+    }
+    lines
+}
+
+impl Placed<'_> {
+    fn describe(&self) -> String {
+        let content = match self.cell.text() {
+            Some(text) => format!("{:?}", text),
+            None => format!("{} children", self.cell.children().len()),
+        };
+        format!("{:?} {}", self.path, content)
+    }
+}
+
+impl<T: DrawCell> Table<T> {
+    // Table with every grid unit drawn in the free space and every leaf cell
+    // numbered on its top border, followed by the list of cells with their bounds.
+    pub fn render_debug(&self) -> Vec<String> {
+        let rows = lines(&self.row_heights());
+        let cols = lines(&self.column_widths());
+        // the canvas knows which points are borders, text or the tail of a wide char
+        let mut canvas = self.canvas();
+        let blank = |canvas: &Canvas, y: usize, x: usize| {
+            y < canvas.height() && x < canvas.width() && canvas.line(y, x) == Some(Arms::default())
+        };
+
+        let leaves: Vec<Placed> = self
+            .placed()
+            .into_iter()
+            .filter(|p| p.cell.children().is_empty())
+            .collect();
+
+        // box around the text of every leaf, the grid is not drawn over it
+        let text_boxes: Vec<(usize, usize, usize, usize)> = leaves
+            .iter()
+            .filter_map(|placed| {
+                let (row, col, height, width) = placed.rect;
+                let ys = rows[row] + 1..rows[row + height];
+                let xs = cols[col] + 1..cols[col + width];
+                let used: Vec<(usize, usize)> = ys
+                    .flat_map(|y| xs.clone().map(move |x| (y, x)))
+                    .filter(|&(y, x)| y < canvas.height() && !blank(&canvas, y, x))
+                    .collect();
+                let top = used.iter().map(|&(y, _)| y).min()?;
+                let bottom = used.iter().map(|&(y, _)| y).max()?;
+                let left = used.iter().map(|&(_, x)| x).min()?;
+                let right = used.iter().map(|&(_, x)| x).max()?;
+                Some((top, bottom, left, right))
+            })
+            .collect();
+        let in_text = |y: usize, x: usize| {
+            text_boxes.iter().any(|&(top, bottom, left, right)| {
+                (top..=bottom).contains(&y) && (left..=right).contains(&x)
+            })
+        };
+
+        for y in 0..rows[rows.len() - 1] {
+            for x in 0..cols[cols.len() - 1] {
+                if !blank(&canvas, y, x) || in_text(y, x) {
+                    continue;
+                }
+                let ch = match (rows.contains(&y), cols.contains(&x)) {
+                    (true, true) => GRID_CROSS,
+                    (true, false) => GRID_HORIZONTAL,
+                    (false, true) => GRID_VERTICAL,
+                    (false, false) => continue,
+                };
+                canvas.plain(y, x, ch.encode_utf8(&mut [0; 4]), x + 1);
+            }
+        }
+
+        let mut legend = vec![];
+        for (number, placed) in leaves.iter().enumerate() {
+            let (row, col, height, width) = placed.rect;
+            let label = format!("#{}", number + 1);
+            // labels go over the top border, so the content stays readable, a cell
+            // without room for all of it or with a title there is only in the legend
+            let (y, x) = (rows[row], cols[col] + 1);
+            let room = cols[col + width] - x;
+            let free =
+                (x..x + label.len()).all(|x| x < canvas.width() && canvas.line(y, x).is_some());
+            if label.len() <= room && free {
+                canvas.plain(y, x, &label, x + label.len());
+            }
+
+            let bounds = placed.cell.get_config().bounds;
+            legend.push(format!(
+                "{} {} at {},{} span {}x{} min {}x{} rec {}x{}",
+                label,
+                placed.describe(),
+                row,
+                col,
+                height,
+                width,
+                bounds.min.pt_height,
+                bounds.min.pt_width,
+                bounds.rec.pt_height,
+                bounds.rec.pt_width,
+            ));
+        }
+
+        canvas
+            .render()
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).to_string())
+            .chain(legend)
+            .collect()
+    }
+
+    // Replays the grid fixup and names the cell that last widened every column.
+    // Widths are in grid units, each unit also carries one border character.
+    pub fn explain(&self) -> Vec<String> {
+        let actual = self.column_widths();
+        let mut widths = vec![0; actual.len()];
+        let mut forced_by: Vec<Option<(String, usize, usize)>> = vec![None; actual.len()];
+
+        for placed in self.placed() {
+            let (_, col, _, width) = placed.rect;
+            let rec = placed.cell.get_config().bounds.rec.pt_width;
+            let before = widths[col..col + width].to_vec();
+            increase_to_size(&mut widths[col..col + width], rec);
+            for (i, old) in before.into_iter().enumerate() {
+                if widths[col + i] != old {
+                    forced_by[col + i] = Some((placed.describe(), rec, width));
+                }
+            }
+        }
+
+        (0..actual.len())
+            .map(|i| {
+                let reason = match &forced_by[i] {
+                    _ if widths[i] != actual[i] => "resized by hand".to_string(),
+                    Some((cell, rec, 1)) => format!("forced by {} (rec width {})", cell, rec),
+                    Some((cell, rec, span)) => format!(
+                        "forced by {} (rec width {} over {} columns)",
+                        cell, rec, span
+                    ),
+                    None => "not constrained".to_string(),
+                };
+                format!("column {}: width {}, {}", i, actual[i], reason)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::{Col, Row, TextCell};
+    use crate::config::{CellConfig, UserProperties};
+
+    fn text(text: &str, span_width: usize) -> Box<dyn DrawCell> {
+        let props = UserProperties {
+            border: crate::borders::Width::Light,
+            span_width,
+            ..UserProperties::default()
+        };
+        Box::new(TextCell::new(text.to_string(), CellConfig::from(props)))
+    }
+
+    fn table() -> Table<Col> {
+        let top = Row::new(vec![text("a", 1), text("bb", 1)], CellConfig::default());
+        let bottom = Row::new(vec![text("wide title", 2)], CellConfig::default());
+        Table::from(Col::new(
+            vec![Box::new(top), Box::new(bottom)],
+            CellConfig::default(),
+        ))
+    }
+
+    #[test]
+    fn explain_columns() {
+        let mut table = table();
+        assert_eq!(
+            vec![
+                "column 0: width 5, forced by [1, 0] \"wide title\" (rec width 11 over 2 columns)",
+                "column 1: width 6, forced by [1, 0] \"wide title\" (rec width 11 over 2 columns)",
+            ],
+            table.explain()
+        );
+
+        assert!(table.resize_column(1, 8));
+        assert_eq!("column 1: width 8, resized by hand", table.explain()[1]);
+    }
+
+    #[test]
+    fn debug_overlay() {
+        assert_eq!(
+            vec![
+                "┌#1──┬#2───┐",
+                "│ a  │ bb  │",
+                "├#3──┴─────┤",
+                "│wide title│",
+                "└──────────┘",
+                "#1 [0, 0] \"a\" at 0,0 span 1x1 min 2x2 rec 2x2",
+                "#2 [0, 1] \"bb\" at 0,1 span 1x1 min 2x2 rec 2x3",
+                "#3 [1, 0] \"wide title\" at 1,0 span 1x2 min 2x2 rec 2x11",
+            ],
+            table().render_debug()
+        );
+    }
+
+    #[test]
+    fn debug_overlay_around_text() {
+        let cells = ["a", "b", "c", "d"].iter().map(|t| text(t, 1)).collect();
+        let top = Row::new(cells, CellConfig::default());
+        let bottom = Row::new(vec![text("x y", 4)], CellConfig::default());
+        let table = Table::from(Col::new(
+            vec![Box::new(top), Box::new(bottom)],
+            CellConfig::default(),
+        ));
+        assert_eq!(
+            vec![
+                "┌─┬─┬─┬─┐",
+                "│a│b│c│d│",
+                "├#5─┴─┴─┤",
+                "│ ╎x y╎ │",
+                "└───────┘"
+            ],
+            table.render_debug()[..5].to_vec()
+        );
+    }
+
+    #[test]
+    fn debug_overlay_with_wide_chars() {
+        let top = Row::new(vec![text("a", 1), text("表表", 1)], CellConfig::default());
+        let bottom = Row::new(vec![text("wide title", 2)], CellConfig::default());
+        let table = Table::from(Col::new(
+            vec![Box::new(top), Box::new(bottom)],
+            CellConfig::default(),
+        ));
+        assert_eq!("┌#1─┬#2────┐", table.render_debug()[0]);
+
+        // the title keeps its place, the labels under it are left out
+        let table = table.title("表表表表", crate::config::Alignment::Right);
+        assert_eq!(
+            vec![
+                "┌ 表┬表表表┐",
+                "│ a │ 表表 │",
+                "├#3─┴──────┤",
+                "│wide title│",
+                "└──────────┘",
+            ],
+            table.render_debug()[..5].to_vec()
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use super::debug::lines;
+use super::{Canvas, DrawCell, Table};
+use crate::borders::{Arms, Width};
+use crate::config::Alignment;
+
+// a leaf cell on the outline grid
+pub(super) struct Entry {
+    pub row: usize,
+    pub col: usize,
+    pub height: usize,
+    pub width: usize,
+    pub text: String,
+    pub alignment: Alignment,
+    pub header: bool,
+}
+
+// Leaves of a table on the coarsest grid that still separates all of them,
+// together with the rules that were painted between them. Markup backends
+// work on it instead of the cell tree, which may nest rows and columns freely.
+pub(super) struct Outline {
+    pub entries: Vec<Entry>,
+    // char offsets of the lines of the outline grid in the rendered table
+    pub rows: Vec<usize>,
+    pub cols: Vec<usize>,
+    pub title: Option<String>,
+    cover: Vec<usize>,
+    canvas: Canvas,
+}
+
+impl Outline {
+    pub fn height(&self) -> usize {
+        self.rows.len() - 1
+    }
+
+    pub fn width(&self) -> usize {
+        self.cols.len() - 1
+    }
+
+    // index into entries of the leaf covering the unit
+    pub fn cover(&self, row: usize, col: usize) -> usize {
+        self.cover[row * self.width() + col]
+    }
+
+    pub fn covering(&self, row: usize, col: usize) -> &Entry {
+        &self.entries[self.cover(row, col)]
+    }
+
+    // rule over the column on the line above the row, row == height for the bottom line
+    pub fn horizontal(&self, row: usize, col: usize) -> Width {
+        let y = self.rows[row];
+        (self.cols[col] + 1..self.cols[col + 1])
+            .filter_map(|x| self.canvas.line(y, x))
+            .fold(Width::None, |width, arms| combine(width, &arms, true))
+    }
+
+    // rule next to the row on the line left of the column, col == width for the right line
+    pub fn vertical(&self, row: usize, col: usize) -> Width {
+        let x = self.cols[col];
+        (self.rows[row] + 1..self.rows[row + 1])
+            .filter_map(|y| self.canvas.line(y, x))
+            .fold(Width::None, |width, arms| combine(width, &arms, false))
+    }
+
+    // leading rows made of header cells only
+    pub fn header_rows(&self) -> usize {
+        let rows = (0..self.height())
+            .take_while(|&row| (0..self.width()).all(|col| self.covering(row, col).header))
+            .count();
+        // the line under the header must not cut through a cell
+        (1..=rows)
+            .rev()
+            .find(|&row| row == self.height() || self.starts_row(row))
+            .unwrap_or(0)
+    }
+
+    // no cell goes on over the line above the row
+    pub fn starts_row(&self, row: usize) -> bool {
+        (0..self.width()).all(|col| self.covering(row, col).row == row)
+    }
+}
+
+fn combine(width: Width, arms: &Arms, horizontal: bool) -> Width {
+    let (a, b) = match horizontal {
+        true => (arms.left, arms.right),
+        false => (arms.up, arms.down),
+    };
+    *width.combine(a.combine(&b))
+}
+
+// indices of the units that start a line of the outline
+fn boundaries(edges: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut edges: Vec<usize> = edges.collect();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+impl<T: DrawCell> Table<T> {
+    pub(super) fn outline(&self) -> Outline {
+        let leaves: Vec<_> = self
+            .placed()
+            .into_iter()
+            .filter(|placed| placed.cell.children().is_empty())
+            .collect();
+
+        let row_units = boundaries(
+            leaves
+                .iter()
+                .flat_map(|p| vec![p.rect.0, p.rect.0 + p.rect.2]),
+        );
+        let col_units = boundaries(
+            leaves
+                .iter()
+                .flat_map(|p| vec![p.rect.1, p.rect.1 + p.rect.3]),
+        );
+        let row_lines = lines(&self.row_heights());
+        let col_lines = lines(&self.column_widths());
+        let find = |units: &[usize], unit: usize| units.binary_search(&unit).unwrap();
+
+        let width = col_units.len() - 1;
+        let mut cover = vec![0; (row_units.len() - 1) * width];
+        let entries: Vec<Entry> = leaves
+            .iter()
+            .enumerate()
+            .map(|(index, placed)| {
+                let (row, col, height, span) = placed.rect;
+                let (row, col) = (find(&row_units, row), find(&col_units, col));
+                let height = find(&row_units, placed.rect.0 + height) - row;
+                let span = find(&col_units, placed.rect.1 + span) - col;
+                for r in row..row + height {
+                    for c in col..col + span {
+                        cover[r * width + c] = index;
+                    }
+                }
+                Entry {
+                    row,
+                    col,
+                    height,
+                    width: span,
+                    text: placed.cell.text().unwrap_or("").to_string(),
+                    alignment: placed.cell.get_config().alignment,
+                    header: placed.classes.iter().any(|class| class == "header"),
+                }
+            })
+            .collect();
+
+        Outline {
+            entries,
+            rows: row_units.iter().map(|&unit| row_lines[unit]).collect(),
+            cols: col_units.iter().map(|&unit| col_lines[unit]).collect(),
+            title: self.title_text().map(str::to_string),
+            cover,
+            canvas: self.canvas(),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use console::Color;
+
+use crate::{
+    borders::CellBorder,
+    config::{Bound, CellBounds, CellConfig, Style},
+};
+
+#[cfg(feature = "rayon")]
+use super::cell::parts::{self as cell_parts, Part};
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError, Threshold};
+use super::text_cell::{box_align, pad};
+use super::{Cell, CellView, Draw, GridSlice, GridSliceMut};
+
+// default narrowest bar, a gauge can be made narrower or wider than this
+const MIN_BAR_WIDTH: usize = 10;
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const FULL_BLOCK: char = '█';
+
+#[derive(Debug)]
+pub struct GaugeCell {
+    fraction: f64,
+    label: bool,
+    min_width: usize,
+    thresholds: Vec<(f64, Color)>,
+    base: CellConfig,
+    config: CellConfig,
+}
+
+impl GaugeCell {
+    pub fn new(fraction: f64, config: CellConfig) -> Self {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        let mut cell = Self {
+            fraction,
+            label: false,
+            min_width: MIN_BAR_WIDTH,
+            thresholds: vec![],
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
+    }
+
+    pub fn label(mut self, label: bool) -> Self {
+        self.label = label;
+        self
+    }
+
+    pub fn min_width(mut self, width: usize) -> Self {
+        self.min_width = width;
+        self.reset_config();
+        self
+    }
+
+    // colour is picked from the highest threshold not exceeding the fraction
+    pub fn threshold(mut self, at: f64, color: Color) -> Self {
+        self.thresholds.push((at, color));
+        self.thresholds
+            .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        self
+    }
+
+    fn color(&self) -> Option<Color> {
+        self.thresholds
+            .iter()
+            .rev()
+            .find(|(at, _)| *at <= self.fraction)
+            .map(|(_, color)| *color)
+    }
+
+    fn bar(&self, width: usize) -> String {
+        let eighths = (self.fraction * (width * 8) as f64).round() as usize;
+        let full = eighths / 8;
+        let partial = eighths % 8;
+
+        let mut bar = vec![FULL_BLOCK; full];
+        if partial > 0 {
+            bar.push(PARTIAL_BLOCKS[partial]);
+        }
+        bar.resize(width, ' ');
+
+        if self.label {
+            let label = format!("{:.0}%", self.fraction * 100.0);
+            let len = label.chars().count();
+            if len <= width {
+                let start = (width - len) / 2;
+                for (i, ch) in label.chars().enumerate() {
+                    bar[start + i] = ch;
+                }
+            }
+        }
+
+        bar.into_iter().collect()
+    }
+
+    // the threshold colour goes over the foreground of the cell style
+    fn style(&self) -> Style {
+        match self.color() {
+            Some(color) => self.config.style.fg(color),
+            None => self.config.style,
+        }
+    }
+}
+
+impl Cell for GaugeCell {
+    fn get_config(&self) -> &CellConfig {
+        &self.config
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        &mut self.config
+    }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
+    fn debug_str(&self) -> String {
+        format!("{:?}", self)
+    }
+    fn reset_config(&mut self) {
+        self.config = CellConfig {
+            bounds: self.base.bounds + CellBounds::from_width(self.min_width, self.base.padding),
+            ..self.base
+        };
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Gauge {
+            fraction: self.fraction,
+            label: self.label,
+            min_width: self.min_width,
+            thresholds: self
+                .thresholds
+                .iter()
+                .map(|&(at, color)| Threshold {
+                    at,
+                    color: Some(color),
+                })
+                .collect(),
+            base: self.base,
+            config: self.config,
+            rect,
+        })
+    }
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        cell_parts::leaf(self, grid, top, left, parts)
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.fixup_config_default(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.fixup_grid_default(grid)
+    }
+}
+
+impl Draw for GaugeCell {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+        let padding = self.config.padding;
+        let bar = self.bar(pt_width.saturating_sub(2 * padding));
+        let style = self.style();
+        let textbox = box_align(vec![pad(&bar, padding)], pt_height, pt_width)
+            .iter()
+            .map(|line| style.apply(line))
+            .collect();
+
+        CellView::new(
+            textbox,
+            CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border),
+        )
+    }
+}
+
+#[macro_export]
+macro_rules! gaugecell {
+    ({$($i:ident=$e:expr),*}, $x:expr) => {{
+        let config = $crate::config::CellConfig::from(properties!(border = Light, $($i=$e),*));
+
+        $crate::cells::GaugeCell::new($x as f64, config)
+    }};
+
+    ($x:expr, {$($i:ident=$e:expr),*}) => {{
+        gaugecell!({$($i=$e),*}, $x)
+    }};
+
+    ($x:expr) => {{
+        gaugecell!({}, $x)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UserProperties;
+
+    fn gauge(fraction: f64) -> GaugeCell {
+        GaugeCell::new(fraction, CellConfig::default())
+    }
+
+    #[test]
+    fn bar_eighths() {
+        assert_eq!("█████     ", gauge(0.5).bar(10));
+        assert_eq!("████▌     ", gauge(0.45).bar(10));
+        assert_eq!("▏   ", gauge(0.03).bar(4));
+        assert_eq!("    ", gauge(-1.0).bar(4));
+        assert_eq!("████", gauge(2.0).bar(4));
+    }
+
+    #[test]
+    fn bar_label() {
+        assert_eq!("███50%    ", gauge(0.5).label(true).bar(10));
+        assert_eq!("█", gauge(1.0).label(true).bar(1));
+    }
+
+    #[test]
+    fn min_width() {
+        let props = UserProperties {
+            padding: 1,
+            ..UserProperties::default()
+        };
+        let cell = GaugeCell::new(0.5, CellConfig::from(props));
+        assert_eq!(MIN_BAR_WIDTH + 3, cell.get_config().bounds.rec.pt_width);
+        let cell = cell.min_width(4);
+        assert_eq!(4 + 3, cell.get_config().bounds.rec.pt_width);
+    }
+
+    #[test]
+    fn threshold_color() {
+        let cell = gauge(0.7)
+            .threshold(0.9, Color::Red)
+            .threshold(0.0, Color::Green)
+            .threshold(0.6, Color::Yellow);
+        assert_eq!(Some(Color::Yellow), cell.color());
+        assert_eq!(None, gauge(0.7).color());
+    }
+}
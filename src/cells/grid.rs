@@ -2,7 +2,8 @@ use std::slice::SliceIndex;
 
 use crate::config::{Bound, CellConfig};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Grid {
     pub heights: Vec<usize>,
     pub widths: Vec<usize>,
@@ -28,14 +29,14 @@ impl Grid {
         }
     }
 
-    pub fn slice_mut(&mut self) -> GridSliceMut {
+    pub fn slice_mut(&mut self) -> GridSliceMut<'_> {
         GridSliceMut {
             heights: &mut self.heights,
             widths: &mut self.widths,
         }
     }
 
-    pub fn slice(&self) -> GridSlice {
+    pub fn slice(&self) -> GridSlice<'_> {
         GridSlice {
             heights: &self.heights,
             widths: &self.widths,
@@ -44,7 +45,7 @@ impl Grid {
 }
 
 impl GridSliceMut<'_> {
-    pub fn slice_mut<T, U>(&mut self, range_height: T, range_width: U) -> GridSliceMut
+    pub fn slice_mut<T, U>(&mut self, range_height: T, range_width: U) -> GridSliceMut<'_>
     where
         T: SliceIndex<[usize], Output = [usize]>,
         U: SliceIndex<[usize], Output = [usize]>,
@@ -63,8 +64,9 @@ impl GridSliceMut<'_> {
     }
 }
 
-impl GridSlice<'_> {
-    pub fn slice<T, U>(&self, range_height: T, range_width: U) -> GridSlice
+impl<'a> GridSlice<'a> {
+    // the slice borrows the grid, not this slice of it
+    pub fn slice<T, U>(&self, range_height: T, range_width: U) -> GridSlice<'a>
     where
         T: SliceIndex<[usize], Output = [usize]>,
         U: SliceIndex<[usize], Output = [usize]>,
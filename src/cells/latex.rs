@@ -0,0 +1,311 @@
+use super::export::{Entry, Outline};
+use super::{DrawCell, Table};
+use crate::borders::Width;
+use crate::config::Alignment;
+
+// how the borders of the table turn into LaTeX rules
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Latex {
+    // \hline, \cline and vertical rules in the column specs
+    Tabular,
+    // \toprule, \midrule, \cmidrule and \bottomrule of the booktabs package, no vertical rules
+    Booktabs,
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\n' => escaped.push(' '),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn align(alignment: Alignment) -> char {
+    match alignment {
+        Alignment::Left => 'l',
+        Alignment::Right => 'r',
+        Alignment::Default | Alignment::Center => 'c',
+    }
+}
+
+fn vrule(width: Width) -> &'static str {
+    match width {
+        Width::None => "",
+        Width::Light => "|",
+        Width::Heavy | Width::Block => "||",
+    }
+}
+
+// contiguous runs of drawn segments, 1-based as \cline wants them
+fn segments(rules: &[Width]) -> Vec<(usize, usize)> {
+    let mut segments: Vec<(usize, usize)> = vec![];
+    for (col, rule) in rules.iter().enumerate() {
+        if *rule == Width::None {
+            continue;
+        }
+        match segments.last_mut() {
+            Some((_, end)) if *end == col => *end = col + 1,
+            _ => segments.push((col + 1, col + 1)),
+        }
+    }
+    segments
+}
+
+struct Writer<'a> {
+    outline: &'a Outline,
+    rules: Latex,
+    // alignment of every column of the outline
+    columns: Vec<char>,
+}
+
+impl Writer<'_> {
+    fn vertical(&self, row: usize, col: usize) -> &'static str {
+        match self.rules {
+            Latex::Tabular => vrule(self.outline.vertical(row, col)),
+            Latex::Booktabs => "",
+        }
+    }
+
+    fn spec(&self) -> String {
+        let mut spec = String::new();
+        for (col, align) in self.columns.iter().enumerate() {
+            spec.push_str(self.spec_rule(col));
+            spec.push(*align);
+        }
+        spec.push_str(self.spec_rule(self.outline.width()));
+        spec
+    }
+
+    fn rule(&self, row: usize) -> Vec<String> {
+        let outline = self.outline;
+        let widths: Vec<Width> = (0..outline.width())
+            .map(|col| outline.horizontal(row, col))
+            .collect();
+        let segments = segments(&widths);
+        let full = segments == [(1, outline.width())];
+        let heavy = widths
+            .iter()
+            .any(|w| *w == Width::Heavy || *w == Width::Block);
+
+        match self.rules {
+            _ if segments.is_empty() => vec![],
+            Latex::Booktabs if row == 0 => vec!["\\toprule".to_string()],
+            Latex::Booktabs if row == outline.height() => vec!["\\bottomrule".to_string()],
+            Latex::Booktabs if full && heavy => vec!["\\midrule[\\heavyrulewidth]".to_string()],
+            Latex::Booktabs if full => vec!["\\midrule".to_string()],
+            Latex::Booktabs => segments
+                .iter()
+                .map(|(a, b)| format!("\\cmidrule(lr){{{}-{}}}", a, b))
+                .collect(),
+            Latex::Tabular if full && heavy => vec!["\\hline\\hline".to_string()],
+            Latex::Tabular if full => vec!["\\hline".to_string()],
+            Latex::Tabular => segments
+                .iter()
+                .map(|(a, b)| format!("\\cline{{{}-{}}}", a, b))
+                .collect(),
+        }
+    }
+
+    // cell at (row, col), only the first row of a tall cell carries its text
+    fn cell(&self, entry: &Entry, row: usize) -> String {
+        let mut text = match entry.row == row {
+            true => escape(&entry.text),
+            false => String::new(),
+        };
+        if entry.height > 1 && entry.row == row {
+            text = format!("\\multirow{{{}}}{{*}}{{{}}}", entry.height, text);
+        }
+
+        let align = align(entry.alignment);
+        let left = match entry.col {
+            0 => self.vertical(row, 0),
+            _ => "",
+        };
+        let right = self.vertical(row, entry.col + entry.width);
+        // the spec puts the left rule only before the first column
+        let spec_left = match entry.col {
+            0 => self.spec_rule(0),
+            _ => "",
+        };
+        let own_rules = left != spec_left || right != self.spec_rule(entry.col + entry.width);
+        if entry.width > 1 || align != self.columns[entry.col] || own_rules {
+            format!(
+                "\\multicolumn{{{}}}{{{}{}{}}}{{{}}}",
+                entry.width, left, align, right, text
+            )
+        } else {
+            text
+        }
+    }
+
+    // the most common rule on the line, cells with another one get their own spec
+    fn spec_rule(&self, col: usize) -> &'static str {
+        let widths: Vec<Width> = (0..self.outline.height())
+            .map(|row| self.outline.vertical(row, col))
+            .collect();
+        let width = [Width::None, Width::Light, Width::Heavy, Width::Block]
+            .iter()
+            .max_by_key(|width| widths.iter().filter(|w| w == width).count())
+            .unwrap();
+        match self.rules {
+            Latex::Tabular => vrule(*width),
+            Latex::Booktabs => "",
+        }
+    }
+
+    fn write(&self) -> Vec<String> {
+        let outline = self.outline;
+        let mut lines = vec![format!("\\begin{{tabular}}{{{}}}", self.spec())];
+        for row in 0..outline.height() {
+            lines.extend(self.rule(row));
+
+            let mut cells = vec![];
+            let mut col = 0;
+            while col < outline.width() {
+                let entry = outline.covering(row, col);
+                cells.push(self.cell(entry, row));
+                col += entry.width;
+            }
+            lines.push(format!("{} \\\\", cells.join(" & ")));
+        }
+        lines.extend(self.rule(outline.height()));
+        lines.push("\\end{tabular}".to_string());
+        lines
+    }
+}
+
+impl<T: DrawCell> Table<T> {
+    // LaTeX tabular with the spans of the table, tall cells need the multirow package
+    // and the booktabs rules the booktabs package.
+    pub fn render_latex(&self, rules: Latex) -> Vec<String> {
+        let outline = self.outline();
+        // alignment of the first cell that fills a single column, the header goes last
+        let columns = (0..outline.width())
+            .map(|col| {
+                let mut candidates: Vec<&Entry> = outline
+                    .entries
+                    .iter()
+                    .filter(|entry| entry.col == col && entry.width == 1)
+                    .collect();
+                candidates.sort_by_key(|entry| entry.header);
+                candidates
+                    .first()
+                    .map_or('c', |entry| align(entry.alignment))
+            })
+            .collect();
+
+        Writer {
+            outline: &outline,
+            rules,
+            columns,
+        }
+        .write()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::{Col, Row, TextCell};
+    use crate::config::{CellConfig, UserProperties};
+
+    fn text(text: &str, props: UserProperties) -> Box<dyn DrawCell> {
+        Box::new(TextCell::new(text.to_string(), CellConfig::from(props)))
+    }
+
+    fn table() -> Table<Col> {
+        let light = UserProperties {
+            border: Width::Light,
+            ..UserProperties::default()
+        };
+        let header = Row::new(
+            vec![
+                text("item", light),
+                text("share", light),
+                text("note", light),
+            ],
+            CellConfig::from(UserProperties {
+                border: Width::Heavy,
+                ..UserProperties::default()
+            }),
+        );
+        let tall = Col::new(
+            vec![
+                text("a_1", light),
+                text(
+                    "b",
+                    UserProperties {
+                        alignment: Alignment::Right,
+                        ..light
+                    },
+                ),
+            ],
+            CellConfig::default(),
+        );
+        let body = Row::new(
+            vec![Box::new(tall), text("50%", light), text("x & y", light)],
+            CellConfig::default(),
+        );
+        let total = text(
+            "total",
+            UserProperties {
+                span_width: 3,
+                ..light
+            },
+        );
+        Table::from(Col::new(
+            vec![Box::new(header), Box::new(body), total],
+            CellConfig::default(),
+        ))
+    }
+
+    #[test]
+    fn tabular() {
+        assert_eq!(
+            vec![
+                "\\begin{tabular}{|c|c|c|}",
+                "\\hline\\hline",
+                "\\multicolumn{1}{||c|}{item} & share & \\multicolumn{1}{c||}{note} \\\\",
+                "\\hline\\hline",
+                "a\\_1 & \\multirow{2}{*}{50\\%} & \\multirow{2}{*}{x \\& y} \\\\",
+                "\\cline{1-1}",
+                "\\multicolumn{1}{|r|}{b} &  &  \\\\",
+                "\\hline",
+                "\\multicolumn{3}{|c|}{total} \\\\",
+                "\\hline",
+                "\\end{tabular}",
+            ],
+            table().render_latex(Latex::Tabular)
+        );
+    }
+
+    #[test]
+    fn booktabs() {
+        assert_eq!(
+            vec![
+                "\\begin{tabular}{ccc}",
+                "\\toprule",
+                "item & share & note \\\\",
+                "\\midrule[\\heavyrulewidth]",
+                "a\\_1 & \\multirow{2}{*}{50\\%} & \\multirow{2}{*}{x \\& y} \\\\",
+                "\\cmidrule(lr){1-1}",
+                "\\multicolumn{1}{r}{b} &  &  \\\\",
+                "\\midrule",
+                "\\multicolumn{3}{c}{total} \\\\",
+                "\\bottomrule",
+                "\\end{tabular}",
+            ],
+            table().render_latex(Latex::Booktabs)
+        );
+    }
+}
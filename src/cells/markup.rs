@@ -0,0 +1,294 @@
+use super::export::{Entry, Outline};
+use super::{DrawCell, Table};
+use crate::config::Alignment;
+
+// lines of the cell wrapped to the width, as many as the cell is tall
+fn content(entry: &Entry, width: usize, height: usize) -> Vec<String> {
+    let mut lines: Vec<String> = entry
+        .text
+        .lines()
+        .flat_map(|line| textwrap::wrap(line, width.max(1)))
+        .map(|line| console::pad_str(&line, width, console::Alignment::Left, None).into_owned())
+        .take(height)
+        .collect();
+    lines.resize(height, " ".repeat(width));
+    lines
+}
+
+// reStructuredText grid table on the layout of the rendered table,
+// every column gets a space of margin on both sides
+struct GridTable<'a> {
+    outline: &'a Outline,
+    xs: Vec<usize>,
+    lines: Vec<Vec<String>>,
+    header: Option<usize>,
+}
+
+impl<'a> GridTable<'a> {
+    fn new(outline: &'a Outline) -> Self {
+        let mut xs = vec![0];
+        for col in 0..outline.width() {
+            let x = xs[col] + outline.cols[col + 1] - outline.cols[col] + 2;
+            xs.push(x);
+        }
+
+        let lines = outline
+            .entries
+            .iter()
+            .map(|entry| {
+                let width = xs[entry.col + entry.width] - xs[entry.col] - 1;
+                let height = outline.rows[entry.row + entry.height] - outline.rows[entry.row] - 1;
+                content(entry, width - 2, height)
+                    .into_iter()
+                    .map(|line| format!(" {} ", line))
+                    .collect()
+            })
+            .collect();
+
+        // the header line has to separate the header from a body
+        let header =
+            Some(outline.header_rows()).filter(|&rows| rows > 0 && rows < outline.height());
+
+        Self {
+            outline,
+            xs,
+            lines,
+            header,
+        }
+    }
+
+    // the cell goes on over the line above the row
+    fn crossing(&self, row: usize, col: usize) -> bool {
+        let outline = self.outline;
+        row > 0 && row < outline.height() && outline.cover(row - 1, col) == outline.cover(row, col)
+    }
+
+    // a vertical line reaches the point from the row
+    fn vertical(&self, row: usize, col: usize) -> bool {
+        let outline = self.outline;
+        col == 0 || col == outline.width() || outline.cover(row, col - 1) != outline.cover(row, col)
+    }
+
+    fn junction(&self, row: usize, col: usize, fill: char) -> char {
+        let outline = self.outline;
+        let left = col > 0 && !self.crossing(row, col - 1);
+        let right = col < outline.width() && !self.crossing(row, col);
+        let above = row > 0 && self.vertical(row - 1, col);
+        let below = row < outline.height() && self.vertical(row, col);
+        match (left || right, above || below) {
+            (true, true) => '+',
+            (true, false) => fill,
+            (false, _) => '|',
+        }
+    }
+
+    // line y of the table, boundary is the row whose top line it is
+    fn line(&self, y: usize, boundary: Option<usize>, row: usize) -> String {
+        let outline = self.outline;
+        let fill = match boundary {
+            Some(row) if Some(row) == self.header => '=',
+            _ => '-',
+        };
+        let mut line = String::new();
+        let mut col = 0;
+        while col < outline.width() {
+            match boundary {
+                Some(row) if !self.crossing(row, col) => {
+                    line.push(self.junction(row, col, fill));
+                    line.extend(std::iter::repeat_n(
+                        fill,
+                        self.xs[col + 1] - self.xs[col] - 1,
+                    ));
+                    col += 1;
+                }
+                _ => {
+                    let cover = outline.cover(row, col);
+                    let entry = &outline.entries[cover];
+                    line.push(match boundary {
+                        Some(row) => self.junction(row, col, fill),
+                        None => '|',
+                    });
+                    let index = y - outline.rows[entry.row] - 1;
+                    line.push_str(&self.lines[cover][index]);
+                    col += entry.width;
+                }
+            }
+        }
+        line.push(match boundary {
+            Some(row) => self.junction(row, outline.width(), fill),
+            None => '|',
+        });
+        line
+    }
+
+    fn write(&self) -> Vec<String> {
+        let rows = &self.outline.rows;
+        (0..=rows[rows.len() - 1])
+            .map(|y| match rows.binary_search(&y) {
+                Ok(boundary) => self.line(y, Some(boundary), boundary),
+                Err(next) => self.line(y, None, next - 1),
+            })
+            .collect()
+    }
+}
+
+fn asciidoc_cell(entry: &Entry) -> String {
+    let span = match (entry.width, entry.height) {
+        (1, 1) => String::new(),
+        (width, 1) => format!("{}+", width),
+        (1, height) => format!(".{}+", height),
+        (width, height) => format!("{}.{}+", width, height),
+    };
+    let align = match entry.alignment {
+        Alignment::Left => "<",
+        Alignment::Right => ">",
+        Alignment::Default | Alignment::Center => "^",
+    };
+    let text = entry.text.replace('|', "\\|").replace('\n', " +\n");
+    format!("{}{}|{}", span, align, text)
+}
+
+impl<T: DrawCell> Table<T> {
+    // Grid table for Sphinx, merged cells keep their spans. The title goes
+    // to a table directive, a header needs leading rows of header cells.
+    pub fn render_rst(&self) -> Vec<String> {
+        let outline = self.outline();
+        let table = GridTable::new(&outline).write();
+        match &outline.title {
+            Some(title) => vec![format!(".. table:: {}", title), String::new()]
+                .into_iter()
+                .chain(table.into_iter().map(|line| format!("   {}", line)))
+                .collect(),
+            None => table,
+        }
+    }
+
+    // AsciiDoc table with the column widths of the rendered table,
+    // only the first row can be a header there
+    pub fn render_asciidoc(&self) -> Vec<String> {
+        let outline = self.outline();
+        let widths: Vec<String> = (0..outline.width())
+            .map(|col| (outline.cols[col + 1] - outline.cols[col] - 1).to_string())
+            .collect();
+        let mut attributes = format!("cols=\"{}\"", widths.join(","));
+        if outline.header_rows() > 0 {
+            attributes.push_str(",options=\"header\"");
+        }
+
+        let mut lines = vec![];
+        if let Some(title) = &outline.title {
+            lines.push(format!(".{}", title));
+        }
+        lines.push(format!("[{}]", attributes));
+        lines.push("|===".to_string());
+        for row in 0..outline.height() {
+            let mut cells: Vec<&Entry> = outline
+                .entries
+                .iter()
+                .filter(|entry| entry.row == row)
+                .collect();
+            cells.sort_by_key(|entry| entry.col);
+            let cells: Vec<String> = cells.into_iter().map(asciidoc_cell).collect();
+            lines.push(cells.join(" "));
+        }
+        lines.push("|===".to_string());
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::Width;
+    use crate::cells::{ClassCell, Col, Row, TextCell};
+    use crate::config::{CellConfig, UserProperties};
+
+    fn text(text: &str, props: UserProperties) -> Box<dyn DrawCell> {
+        Box::new(TextCell::new(text.to_string(), CellConfig::from(props)))
+    }
+
+    fn table() -> Table<Col> {
+        let light = UserProperties {
+            border: Width::Light,
+            ..UserProperties::default()
+        };
+        let header = Row::new(
+            vec![
+                text("item", light),
+                text("share", light),
+                text("note", light),
+            ],
+            CellConfig::default(),
+        );
+        let tall = Col::new(
+            vec![
+                text("a", light),
+                text(
+                    "b",
+                    UserProperties {
+                        alignment: Alignment::Right,
+                        ..light
+                    },
+                ),
+            ],
+            CellConfig::default(),
+        );
+        let body = Row::new(
+            vec![Box::new(tall), text("50%", light), text("x | y", light)],
+            CellConfig::default(),
+        );
+        let total = text(
+            "total",
+            UserProperties {
+                span_width: 3,
+                ..light
+            },
+        );
+        Table::from(Col::new(
+            vec![
+                Box::new(ClassCell::new(Box::new(header), &["header"])),
+                Box::new(body),
+                total,
+            ],
+            CellConfig::default(),
+        ))
+        .title("Shares", Alignment::Default)
+    }
+
+    #[test]
+    fn rst_grid_table() {
+        assert_eq!(
+            vec![
+                ".. table:: Shares",
+                "",
+                "   +------+-------+-------+",
+                "   | item | share | note  |",
+                "   +======+=======+=======+",
+                "   | a    | 50%   | x | y |",
+                "   +------+       |       |",
+                "   | b    |       |       |",
+                "   +------+-------+-------+",
+                "   | total                |",
+                "   +----------------------+",
+            ],
+            table().render_rst()
+        );
+    }
+
+    #[test]
+    fn asciidoc_table() {
+        assert_eq!(
+            vec![
+                ".Shares",
+                "[cols=\"4,5,5\",options=\"header\"]",
+                "|===",
+                "^|item ^|share ^|note",
+                "^|a .2+^|50% .2+^|x \\| y",
+                ">|b",
+                "3+^|total",
+                "|===",
+            ],
+            table().render_asciidoc()
+        );
+    }
+}
@@ -2,16 +2,29 @@
 mod cell;
 pub use cell::{Cell, Draw, DrawCell};
 
+pub mod path;
+
 mod grid;
 pub use grid::{Grid, GridSlice, GridSliceMut};
 
 mod view;
 pub use view::CellView;
 
+mod canvas;
+pub use canvas::Canvas;
+
 #[macro_use]
 mod text_cell;
 pub use text_cell::TextCell;
 
+#[macro_use]
+mod gauge_cell;
+pub use gauge_cell::GaugeCell;
+
+#[macro_use]
+mod spark_cell;
+pub use spark_cell::SparkCell;
+
 #[macro_use]
 mod row;
 pub use row::Row;
@@ -20,5 +33,33 @@ pub use row::Row;
 mod col;
 pub use col::Col;
 
+mod content_cell;
+pub use content_cell::{Content, ContentCell};
+
+mod rule_cell;
+pub use rule_cell::RuleCell;
+
+mod class_cell;
+pub use class_cell::ClassCell;
+
+mod stylesheet;
+pub use stylesheet::{Parity, Role, Selector, Stylesheet};
+
+mod debug;
+
+mod export;
+
+mod latex;
+pub use latex::Latex;
+
+mod markup;
+
+#[cfg(feature = "serde")]
+pub mod snapshot;
+
 mod table;
-pub use table::Table;
+pub use table::{CaptionPosition, Pages, Table};
+
+#[macro_use]
+mod table_cell;
+pub use table_cell::TableCell;
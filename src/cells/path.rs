@@ -0,0 +1,88 @@
+use super::DrawCell;
+
+// A path is a list of child indices, starting from the root cell
+
+pub fn find<'a>(cell: &'a dyn DrawCell, path: &[usize]) -> Option<&'a dyn DrawCell> {
+    match path.split_first() {
+        Some((&index, rest)) => find(cell.children().get(index)?.as_ref(), rest),
+        None => Some(cell),
+    }
+}
+
+pub fn find_mut<'a>(cell: &'a mut dyn DrawCell, path: &[usize]) -> Option<&'a mut dyn DrawCell> {
+    match path.split_first() {
+        Some((&index, rest)) => find_mut(cell.children_mut()?.get_mut(index)?.as_mut(), rest),
+        None => Some(cell),
+    }
+}
+
+// path to the innermost cell covering the (row, col) unit of the grid
+pub fn locate(cell: &dyn DrawCell, row: usize, col: usize) -> Option<Vec<usize>> {
+    let config = cell.get_config();
+    if row >= config.span_height || col >= config.span_width {
+        return None;
+    }
+    if cell.children().is_empty() {
+        return Some(vec![]);
+    }
+
+    let offsets = cell.child_offsets();
+    for (index, (child, (top, left))) in cell.children().iter().zip(offsets).enumerate() {
+        if row < top || col < left {
+            continue;
+        }
+        if let Some(mut path) = locate(child.as_ref(), row - top, col - left) {
+            path.insert(0, index);
+            return Some(path);
+        }
+    }
+    None
+}
+
+// a cell of the tree with its grid rectangle: (row, col, height, width) in grid units
+pub(super) struct Placed<'a> {
+    pub path: Vec<usize>,
+    pub cell: &'a dyn DrawCell,
+    pub rect: (usize, usize, usize, usize),
+    // own classes and the ones inherited from the ancestors
+    pub classes: Vec<String>,
+}
+
+// cells in the order the grid is fixed up: children first, then the parent
+pub(super) fn placed(root: &dyn DrawCell) -> Vec<Placed<'_>> {
+    let mut cells = vec![];
+    place(root, vec![], (0, 0), &[], &mut cells);
+    cells
+}
+
+fn place<'a>(
+    cell: &'a dyn DrawCell,
+    path: Vec<usize>,
+    (row, col): (usize, usize),
+    inherited: &[String],
+    cells: &mut Vec<Placed<'a>>,
+) {
+    let mut classes = inherited.to_vec();
+    classes.extend(cell.classes().iter().cloned());
+
+    let offsets = cell.child_offsets();
+    for (index, (child, (top, left))) in cell.children().iter().zip(offsets).enumerate() {
+        let mut path = path.clone();
+        path.push(index);
+        place(
+            child.as_ref(),
+            path,
+            (row + top, col + left),
+            &classes,
+            cells,
+        );
+    }
+
+    let config = cell.get_config();
+    cells.push(Placed {
+        path,
+        cell,
+        rect: (row, col, config.span_height, config.span_width),
+        classes,
+    });
+}
@@ -3,30 +3,28 @@ use crate::{
     config::{Bound, CellConfig, Horizontal},
 };
 
-use super::{Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut};
+#[cfg(feature = "rayon")]
+use super::cell::parts::Part;
+#[cfg(feature = "serde")]
+use super::snapshot::{self, Node, Rect, SnapshotError};
+use super::{Canvas, Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut};
 
 #[derive(Debug)]
 pub struct Row {
     cols: Vec<Box<dyn DrawCell>>,
+    base: CellConfig,
     config: CellConfig,
 }
 
 impl Row {
     pub fn new(cols: Vec<Box<dyn DrawCell>>, config: CellConfig) -> Self {
-        let child = cols
-            .iter()
-            .map(|x| *x.get_config())
-            .collect::<Horizontal<_>>()
-            .0;
-        Self {
+        let mut cell = Self {
             cols,
-            config: CellConfig {
-                bounds: config.bounds + child.bounds,
-                span_height: child.span_height,
-                span_width: child.span_width,
-                ..config
-            },
-        }
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
     }
 }
 
@@ -37,10 +35,81 @@ impl Cell for Row {
     fn get_config_mut(&mut self) -> &mut CellConfig {
         &mut self.config
     }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
     fn debug_str(&self) -> String {
         format!("{:?}", self)
     }
 
+    fn reset_config(&mut self) {
+        for cell in self.cols.iter_mut() {
+            cell.reset_config();
+        }
+
+        let child = self
+            .cols
+            .iter()
+            .map(|x| *x.get_config())
+            .collect::<Horizontal<_>>()
+            .0;
+        self.config = CellConfig {
+            bounds: self.base.bounds + child.bounds,
+            span_height: child.span_height,
+            span_width: child.span_width,
+            ..self.base
+        };
+    }
+
+    fn children(&self) -> &[Box<dyn DrawCell>] {
+        &self.cols
+    }
+
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        let mut sync = true;
+        let outer = self.place(grid, top, left, |cell, slice, y, x| {
+            sync = sync && cell.parts(slice, y, x, parts)
+        });
+        parts.push(Part::Border(top, left, outer));
+        sync
+    }
+
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Row {
+            base: self.base,
+            config: self.config,
+            rect,
+            children: snapshot::describe_children(self, rect)?,
+        })
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn DrawCell>>> {
+        Some(&mut self.cols)
+    }
+
+    fn child_offsets(&self) -> Vec<(usize, usize)> {
+        let mut start = 0;
+        self.cols
+            .iter()
+            .map(|cell| {
+                let offset = (0, start);
+                start += cell.get_config().span_width;
+                offset
+            })
+            .collect()
+    }
+
     fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
         self.fixup_config_default(row_ratio, col_ratio);
 
@@ -111,6 +180,43 @@ impl Draw for Row {
 
         CellView::new(textbox, total_border.unwrap().combine(&outer))
     }
+
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        let outer = self.place(grid, top, left, |cell, slice, y, x| {
+            cell.paint(slice, canvas, y, x)
+        });
+        canvas.border(top, left, &outer);
+    }
+}
+
+impl Row {
+    // hands every child its slice and corner, the outer border is left to the caller
+    fn place<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        mut child: impl FnMut(&'a dyn DrawCell, GridSlice<'a>, usize, usize),
+    ) -> CellBorder {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+
+        let mut start = 0;
+        let mut x = left;
+        for col in self.cols.iter() {
+            let len = col.get_config().span_width;
+            let slice = grid.slice(.., start..(start + len));
+            // neighbours share the border column
+            let width: usize = slice.widths.iter().sum();
+            child(col.as_ref(), slice, top, x);
+            start += len;
+            x += width;
+        }
+
+        CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border)
+    }
 }
 
 #[macro_export]
@@ -0,0 +1,115 @@
+use crate::{
+    borders::{CellBorder, Width},
+    config::{Bound, CellConfig},
+};
+
+#[cfg(feature = "rayon")]
+use super::cell::parts::Part;
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError};
+use super::{Canvas, Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut};
+
+// Draws a rule of the given width over the top border of the wrapped cell,
+// so the separator above it gets heavier without touching the other sides.
+// Otherwise the wrapper is transparent, its children are the children of the wrapped cell.
+#[derive(Debug)]
+pub struct RuleCell {
+    cell: Box<dyn DrawCell>,
+    rule: Width,
+}
+
+impl RuleCell {
+    pub fn new(cell: Box<dyn DrawCell>, rule: Width) -> Self {
+        Self { cell, rule }
+    }
+}
+
+impl Cell for RuleCell {
+    fn get_config(&self) -> &CellConfig {
+        self.cell.get_config()
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        self.cell.get_config_mut()
+    }
+    fn get_base(&self) -> &CellConfig {
+        self.cell.get_base()
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        self.cell.get_base_mut()
+    }
+    fn debug_str(&self) -> String {
+        format!("{:?}", self)
+    }
+    fn reset_config(&mut self) {
+        self.cell.reset_config()
+    }
+    fn children(&self) -> &[Box<dyn DrawCell>] {
+        self.cell.children()
+    }
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        let rule = self.rule_border(&grid);
+        let sync = self.cell.parts(grid, top, left, parts);
+        parts.push(Part::Border(top, left, rule));
+        sync
+    }
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn DrawCell>>> {
+        self.cell.children_mut()
+    }
+    fn child_offsets(&self) -> Vec<(usize, usize)> {
+        self.cell.child_offsets()
+    }
+    fn text(&self) -> Option<&str> {
+        self.cell.text()
+    }
+    fn classes(&self) -> &[String] {
+        self.cell.classes()
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Rule {
+            rule: self.rule,
+            cell: Box::new(self.cell.describe(rect)?),
+        })
+    }
+    fn set_text(&mut self, text: String) -> bool {
+        self.cell.set_text(text)
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.cell.fixup_config(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.cell.fixup_grid(grid)
+    }
+}
+
+impl Draw for RuleCell {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        let rule = self.rule_border(&grid);
+        let (textbox, border) = self.cell.draw(grid).unwrap();
+
+        CellView::new(textbox, border.combine(&rule))
+    }
+
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        let rule = self.rule_border(&grid);
+        self.cell.paint(grid, canvas, top, left);
+        canvas.border(top, left, &rule);
+    }
+}
+
+impl RuleCell {
+    fn rule_border(&self, grid: &GridSlice) -> CellBorder {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+        CellBorder::top_rule(pt_height + 2, pt_width + 2, self.rule)
+    }
+}
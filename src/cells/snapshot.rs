@@ -0,0 +1,280 @@
+use std::fmt;
+
+use console::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    borders::Width,
+    config::{color, Alignment, CellConfig},
+};
+
+use super::{
+    CaptionPosition, Cell, ClassCell, Col, DrawCell, GaugeCell, Grid, Row, RuleCell, SparkCell,
+    Table, TableCell, TextCell,
+};
+
+// position and size of a cell in grid units
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub row: usize,
+    pub col: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+// Serialisable description of a cell tree. Only the base configs are used
+// to restore the cells, computed configs and rectangles are there to be looked at.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Node {
+    Text {
+        text: String,
+        base: CellConfig,
+        config: CellConfig,
+        rect: Rect,
+    },
+    Row {
+        base: CellConfig,
+        config: CellConfig,
+        rect: Rect,
+        children: Vec<Node>,
+    },
+    Col {
+        base: CellConfig,
+        config: CellConfig,
+        rect: Rect,
+        children: Vec<Node>,
+    },
+    Rule {
+        rule: Width,
+        cell: Box<Node>,
+    },
+    Class {
+        classes: Vec<String>,
+        cell: Box<Node>,
+    },
+    Gauge {
+        fraction: f64,
+        label: bool,
+        min_width: usize,
+        thresholds: Vec<Threshold>,
+        base: CellConfig,
+        config: CellConfig,
+        rect: Rect,
+    },
+    Spark {
+        values: Vec<f64>,
+        labels: bool,
+        braille: bool,
+        base: CellConfig,
+        config: CellConfig,
+        rect: Rect,
+    },
+    Table {
+        table: Box<Snapshot>,
+        base: CellConfig,
+        config: CellConfig,
+        rect: Rect,
+    },
+}
+
+// colour of a gauge from the fraction on
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Threshold {
+    pub at: f64,
+    #[serde(with = "color")]
+    pub color: Option<Color>,
+}
+
+// the tree holds a cell which has no description
+#[derive(Debug)]
+pub struct SnapshotError {
+    pub cell: String,
+}
+
+impl SnapshotError {
+    pub fn new(cell: &str) -> Self {
+        Self {
+            cell: cell.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot serialise a table holding {}", self.cell)
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl Node {
+    pub fn into_cell(self) -> Box<dyn DrawCell> {
+        let cells = |children: Vec<Node>| children.into_iter().map(Node::into_cell).collect();
+        match self {
+            Node::Text { text, base, .. } => Box::new(TextCell::new(text, base)),
+            Node::Row { base, children, .. } => Box::new(Row::new(cells(children), base)),
+            Node::Col { base, children, .. } => Box::new(Col::new(cells(children), base)),
+            Node::Rule { rule, cell } => Box::new(RuleCell::new(cell.into_cell(), rule)),
+            Node::Class { classes, cell } => Box::new(ClassCell::new(cell.into_cell(), &classes)),
+            Node::Gauge {
+                fraction,
+                label,
+                min_width,
+                thresholds,
+                base,
+                ..
+            } => Box::new(
+                thresholds
+                    .into_iter()
+                    .filter_map(|t| Some((t.at, t.color?)))
+                    .fold(
+                        GaugeCell::new(fraction, base)
+                            .label(label)
+                            .min_width(min_width),
+                        |cell, (at, color)| cell.threshold(at, color),
+                    ),
+            ),
+            Node::Spark {
+                values,
+                labels,
+                braille,
+                base,
+                ..
+            } => Box::new(
+                SparkCell::new(&values, base)
+                    .labels(labels)
+                    .braille(braille),
+            ),
+            Node::Table { table, base, .. } => {
+                let table: Table<Box<dyn DrawCell>> = Table::from(*table);
+                Box::new(TableCell::new(table, base))
+            }
+        }
+    }
+}
+
+// fails on the first child that cannot be described
+pub(super) fn describe_children<C: Cell + ?Sized>(
+    cell: &C,
+    rect: Rect,
+) -> Result<Vec<Node>, SnapshotError> {
+    cell.children()
+        .iter()
+        .zip(cell.child_offsets())
+        .map(|(child, (top, left))| {
+            let config = child.get_config();
+            child.describe(Rect {
+                row: rect.row + top,
+                col: rect.col + left,
+                height: config.span_height,
+                width: config.span_width,
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub grid: Grid,
+    pub root: Node,
+    #[serde(default)]
+    pub title: Option<(String, Alignment)>,
+    #[serde(default)]
+    pub caption: Option<(String, Alignment, CaptionPosition)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::{Content, ContentCell};
+    use crate::config::UserProperties;
+    use crate::data::{Aggregate, DataTable};
+
+    #[test]
+    fn json_round_trip() {
+        let mut data = DataTable::new(&["host", "cpu"]).footer(Aggregate::Sum, &[1]);
+        data.push(&["alpha", "10"]);
+        data.push(&["beta", "200"]);
+        let mut table = Table::from(data).title("load", Alignment::Left);
+        assert!(table.resize_column(1, 6));
+
+        let json = serde_json::to_string(&table).unwrap();
+        let restored: Table<Box<dyn DrawCell>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(table.render(), restored.render());
+
+        let snapshot = restored.snapshot().unwrap();
+        assert_eq!(vec![6, 6], snapshot.grid.widths);
+        match snapshot.root {
+            Node::Col { children, .. } => match &children[2] {
+                Node::Class { classes, cell } => {
+                    assert_eq!(vec!["body"], *classes);
+                    match cell.as_ref() {
+                        Node::Row { rect, .. } => assert_eq!(
+                            Rect {
+                                row: 2,
+                                col: 0,
+                                height: 1,
+                                width: 2
+                            },
+                            *rect
+                        ),
+                        node => panic!("unexpected node {:?}", node),
+                    }
+                }
+                node => panic!("unexpected node {:?}", node),
+            },
+            node => panic!("unexpected node {:?}", node),
+        }
+    }
+
+    #[test]
+    fn built_in_cells() {
+        let light = CellConfig::from(UserProperties {
+            border: Width::Light,
+            ..UserProperties::default()
+        });
+        let inner = Row::new(vec![Box::new(TextCell::new("a".to_string(), light))], light);
+        let cells: Vec<Box<dyn DrawCell>> = vec![
+            Box::new(
+                GaugeCell::new(0.5, light)
+                    .label(true)
+                    .min_width(4)
+                    .threshold(0.25, Color::Red),
+            ),
+            Box::new(SparkCell::new(&[1, 5, 3], light).braille(true)),
+            Box::new(TableCell::new(inner, light)),
+        ];
+        let table = Table::from(Row::new(cells, light));
+
+        let json = serde_json::to_string(&table).unwrap();
+        let restored: Table<Box<dyn DrawCell>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(table.render(), restored.render());
+        assert_eq!(json, serde_json::to_string(&restored).unwrap());
+    }
+
+    #[test]
+    fn opaque_cells() {
+        struct Stars;
+
+        impl Content for Stars {
+            fn measure(&self) -> (usize, usize) {
+                (1, 3)
+            }
+
+            fn render(&self, _height: usize, _width: usize) -> Vec<String> {
+                vec!["***".to_string()]
+            }
+        }
+
+        let cells: Vec<Box<dyn DrawCell>> = vec![
+            Box::new(TextCell::new("a".to_string(), CellConfig::default())),
+            Box::new(ContentCell::new(Stars, CellConfig::default())),
+        ];
+        let table = Table::from(Row::new(cells, CellConfig::default()));
+        let error = table.snapshot().unwrap_err();
+        assert!(error.cell.contains("ContentCell"));
+        let error = serde_json::to_string(&table).unwrap_err();
+        assert!(error.to_string().contains("ContentCell"));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    borders::CellBorder,
+    config::{Bound, CellBounds, CellConfig},
+    data::Aggregate,
+};
+
+#[cfg(feature = "rayon")]
+use super::cell::parts::{self as cell_parts, Part};
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError};
+use super::text_cell::{box_align, pad};
+use super::{Cell, CellView, Draw, GridSlice, GridSliceMut};
+
+const MIN_SPARK_WIDTH: usize = 8;
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const BRAILLE_BASE: u32 = 0x2800;
+// dot bits bottom to top, for the left and right column of a braille char
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x40, 0x04, 0x02, 0x01], [0x80, 0x20, 0x10, 0x08]];
+
+#[derive(Debug)]
+pub struct SparkCell {
+    values: Vec<f64>,
+    labels: bool,
+    braille: bool,
+    base: CellConfig,
+    config: CellConfig,
+}
+
+fn resample(values: &[f64], width: usize) -> Vec<f64> {
+    let count = values.len();
+    if count == 0 {
+        return vec![];
+    }
+
+    (0..width)
+        .map(|i| {
+            let start = i * count / width;
+            let end = ((i + 1) * count / width).max(start + 1);
+            values[start..end].iter().sum::<f64>() / (end - start) as f64
+        })
+        .collect()
+}
+
+fn levels(values: &[f64], min: f64, max: f64, count: usize) -> Vec<usize> {
+    values
+        .iter()
+        .map(|v| {
+            if max > min {
+                (((v - min) / (max - min)) * (count - 1) as f64).round() as usize
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+impl SparkCell {
+    pub fn new<T: Copy + Into<f64>>(values: &[T], config: CellConfig) -> Self {
+        let values: Vec<f64> = values
+            .iter()
+            .map(|&v| v.into())
+            .filter(|v| !v.is_nan())
+            .collect();
+        let mut cell = Self {
+            values,
+            labels: false,
+            braille: false,
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
+    }
+
+    pub fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self.reset_config();
+        self
+    }
+
+    pub fn braille(mut self, braille: bool) -> Self {
+        self.braille = braille;
+        self
+    }
+
+    fn range(&self) -> (f64, f64) {
+        let min = self.values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = self
+            .values
+            .iter()
+            .cloned()
+            .fold(f64::NEG_INFINITY, f64::max);
+        if self.values.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (min, max)
+        }
+    }
+
+    // same precision as the aggregates, float noise never widens the labels
+    fn label_text(&self) -> (String, String) {
+        let (min, max) = self.range();
+        (Aggregate::format(min), Aggregate::format(max))
+    }
+
+    fn sparkline(&self, width: usize) -> String {
+        let (min, max) = self.range();
+        let samples = resample(&self.values, width);
+        let mut line: String = levels(&samples, min, max, SPARKS.len())
+            .into_iter()
+            .map(|level| SPARKS[level])
+            .collect();
+        line.push_str(&" ".repeat(width - samples.len()));
+        line
+    }
+
+    fn braille_lines(&self, height: usize, width: usize) -> Vec<String> {
+        let (min, max) = self.range();
+        let samples = resample(&self.values, 2 * width);
+        let levels = levels(&samples, min, max, 4 * height);
+
+        (0..height)
+            .rev()
+            .map(|row| {
+                (0..width)
+                    .map(|col| {
+                        let mut code = BRAILLE_BASE;
+                        for (side, dots) in BRAILLE_DOTS.iter().enumerate() {
+                            if let Some(&level) = levels.get(2 * col + side) {
+                                // fill everything below the sample, bottom row always has a dot
+                                let filled = (level + 1).saturating_sub(4 * row).min(4);
+                                code += dots.iter().take(filled).sum::<u32>();
+                            }
+                        }
+                        std::char::from_u32(code).unwrap_or(' ')
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn render(&self, height: usize, width: usize) -> Vec<String> {
+        if !self.labels {
+            return if self.braille && height > 1 {
+                self.braille_lines(height, width)
+            } else {
+                vec![self.sparkline(width)]
+            };
+        }
+
+        let (min, max) = self.label_text();
+        if self.braille && height > 1 {
+            let label_width = min.len().max(max.len());
+            let graph_width = width.saturating_sub(label_width + 1);
+            let mut lines = self.braille_lines(height, graph_width);
+            for (i, line) in lines.iter_mut().enumerate() {
+                let label = match i {
+                    0 => &max,
+                    _ if i == height - 1 => &min,
+                    _ => "",
+                };
+                *line = format!("{:>w$} {}", label, line, w = label_width);
+            }
+            lines
+        } else {
+            let graph_width = width.saturating_sub(min.len() + max.len() + 2);
+            vec![format!("{} {} {}", min, self.sparkline(graph_width), max)]
+        }
+    }
+}
+
+impl Cell for SparkCell {
+    fn get_config(&self) -> &CellConfig {
+        &self.config
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        &mut self.config
+    }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
+    fn debug_str(&self) -> String {
+        format!("{:?}", self)
+    }
+    fn reset_config(&mut self) {
+        let mut width = MIN_SPARK_WIDTH;
+        if self.labels {
+            let (min, max) = self.label_text();
+            width += min.len() + max.len() + 2;
+        }
+        self.config = CellConfig {
+            bounds: self.base.bounds + CellBounds::from_width(width, self.base.padding),
+            ..self.base
+        };
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Spark {
+            values: self.values.clone(),
+            labels: self.labels,
+            braille: self.braille,
+            base: self.base,
+            config: self.config,
+            rect,
+        })
+    }
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        cell_parts::leaf(self, grid, top, left, parts)
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.fixup_config_default(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.fixup_grid_default(grid)
+    }
+}
+
+impl Draw for SparkCell {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+        let padding = self.config.padding;
+        let width = pt_width.saturating_sub(2 * padding);
+        let lines = self
+            .render(pt_height, width)
+            .into_iter()
+            .map(|line| {
+                pad(
+                    &console::pad_str(&line, width, console::Alignment::Left, None),
+                    padding,
+                )
+            })
+            .collect();
+        let textbox = box_align(lines, pt_height, pt_width)
+            .iter()
+            .map(|line| self.config.style.apply(line))
+            .collect();
+
+        CellView::new(
+            textbox,
+            CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border),
+        )
+    }
+}
+
+#[macro_export]
+macro_rules! sparkcell {
+    ({$($i:ident=$e:expr),*}, $x:expr) => {{
+        let config = $crate::config::CellConfig::from(properties!(border = Light, $($i=$e),*));
+
+        $crate::cells::SparkCell::new(&$x[..], config)
+    }};
+
+    ($x:expr, {$($i:ident=$e:expr),*}) => {{
+        sparkcell!({$($i=$e),*}, $x)
+    }};
+
+    ($x:expr) => {{
+        sparkcell!({}, $x)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spark(values: &[i32]) -> SparkCell {
+        SparkCell::new(values, CellConfig::default())
+    }
+
+    #[test]
+    fn resample_to_width() {
+        assert_eq!(vec![1.5, 3.5], resample(&[1.0, 2.0, 3.0, 4.0], 2));
+        assert_eq!(vec![1.0, 1.0, 2.0, 2.0], resample(&[1.0, 2.0], 4));
+        assert!(resample(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn sparkline() {
+        assert_eq!("▁▂▃▄▅▆▇█", spark(&[0, 1, 2, 3, 4, 5, 6, 7]).sparkline(8));
+        assert_eq!("▃▇", spark(&[0, 1, 2, 3, 4, 5, 6, 7]).sparkline(2));
+        assert_eq!("▁▁▁", spark(&[5, 5, 5]).sparkline(3));
+        assert_eq!("   ", spark(&[]).sparkline(3));
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(vec!["0 ▁▅█ 8"], spark(&[0, 4, 8]).labels(true).render(1, 7));
+
+        let cell = SparkCell::new(&[0.1, 0.1 + 0.2], CellConfig::default()).labels(true);
+        assert_eq!(("0.1".to_string(), "0.3".to_string()), cell.label_text());
+        assert_eq!(vec!["0.1 ▁█ 0.3"], cell.render(1, 10));
+    }
+
+    #[test]
+    fn braille() {
+        assert_eq!(
+            vec!["⠀⢸", "⣠⣿"],
+            spark(&[0, 1, 3, 7]).braille(true).render(2, 2)
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::config::Patch;
+
+use super::{path, DrawCell};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Role {
+    Header,
+    Body,
+    Footer,
+}
+
+impl Role {
+    // roles are plain classes set by the table builders
+    pub fn class(&self) -> &'static str {
+        match self {
+            Role::Header => "header",
+            Role::Body => "body",
+            Role::Footer => "footer",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+// Every condition that is set has to hold. Rows and columns are counted in cells
+// as they were built, before the spans are stretched to a common grid;
+// the column name is the text of the cell in the first row.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Selector {
+    role: Option<Role>,
+    column: Option<usize>,
+    name: Option<String>,
+    parity: Option<Parity>,
+    depth: Option<usize>,
+    classes: Vec<String>,
+}
+
+// a leaf cell of the tree, as seen by the selectors
+struct Target<'a> {
+    path: Vec<usize>,
+    row: usize,
+    col: usize,
+    classes: Vec<&'a str>,
+}
+
+impl Selector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn role(mut self, role: Role) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn column(mut self, index: usize) -> Self {
+        self.column = Some(index);
+        self
+    }
+
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn parity(mut self, parity: Parity) -> Self {
+        self.parity = Some(parity);
+        self
+    }
+
+    // depth of the cell in the Row/Col tree, children of the root are at depth 1
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn class(mut self, class: &str) -> Self {
+        self.classes.push(class.to_string());
+        self
+    }
+
+    pub fn specificity(&self) -> usize {
+        [
+            self.role.is_some(),
+            self.column.is_some(),
+            self.name.is_some(),
+            self.parity.is_some(),
+            self.depth.is_some(),
+        ]
+        .iter()
+        .filter(|&&set| set)
+        .count()
+            + self.classes.len()
+    }
+
+    fn matches(&self, target: &Target, name: Option<&str>) -> bool {
+        let parity = match target.row % 2 {
+            0 => Parity::Even,
+            _ => Parity::Odd,
+        };
+        self.role
+            .is_none_or(|role| target.classes.contains(&role.class()))
+            && self.column.is_none_or(|column| column == target.col)
+            && self.name.as_deref().is_none_or(|n| Some(n) == name)
+            && self.parity.is_none_or(|p| p == parity)
+            && self.depth.is_none_or(|depth| depth == target.path.len())
+            && self
+                .classes
+                .iter()
+                .all(|class| target.classes.contains(&class.as_str()))
+    }
+}
+
+// A theme file is a stylesheet:
+//
+//     [[rule]]
+//     select = { role = "header" }
+//     set = { border = "heavy", style = { bold = true } }
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(from = "StylesheetDef")
+)]
+pub struct Stylesheet {
+    rules: Vec<(Selector, Patch)>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDef {
+    #[serde(default)]
+    select: Selector,
+    set: Patch,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StylesheetDef {
+    #[serde(default)]
+    rule: Vec<RuleDef>,
+}
+
+#[cfg(feature = "serde")]
+impl From<StylesheetDef> for Stylesheet {
+    fn from(def: StylesheetDef) -> Self {
+        Self {
+            rules: def.rule.into_iter().map(|r| (r.select, r.set)).collect(),
+        }
+    }
+}
+
+fn collect<'a>(
+    cell: &'a dyn DrawCell,
+    path: Vec<usize>,
+    (row, col): (usize, usize),
+    mut classes: Vec<&'a str>,
+    targets: &mut Vec<Target<'a>>,
+) {
+    classes.extend(cell.classes().iter().map(String::as_str));
+    if cell.children().is_empty() {
+        targets.push(Target {
+            path,
+            row,
+            col,
+            classes,
+        });
+        return;
+    }
+
+    let offsets = cell.child_offsets();
+    for (index, (child, (top, left))) in cell.children().iter().zip(offsets).enumerate() {
+        let mut path = path.clone();
+        path.push(index);
+        collect(
+            child.as_ref(),
+            path,
+            (row + top, col + left),
+            classes.clone(),
+            targets,
+        );
+    }
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // on equal specificity the rule added last wins
+    pub fn rule(mut self, selector: Selector, patch: Patch) -> Self {
+        self.rules.push((selector, patch));
+        self
+    }
+
+    // rules of the other stylesheet go after the own ones
+    pub fn merge(mut self, other: &Stylesheet) -> Self {
+        self.rules.extend(other.rules.iter().cloned());
+        self
+    }
+
+    // cascaded patch for every leaf cell of the tree, which must not be fixed up yet
+    pub fn resolve(&self, root: &dyn DrawCell) -> Vec<(Vec<usize>, Patch)> {
+        let mut rules: Vec<&(Selector, Patch)> = self.rules.iter().collect();
+        rules.sort_by_key(|(selector, _)| selector.specificity());
+
+        let mut targets = vec![];
+        collect(root, vec![], (0, 0), vec![], &mut targets);
+
+        targets
+            .into_iter()
+            .filter_map(|target| {
+                let name = path::locate(root, 0, target.col)
+                    .and_then(|path| path::find(root, &path))
+                    .and_then(|cell| cell.text());
+                let patch = rules
+                    .iter()
+                    .filter(|(selector, _)| selector.matches(&target, name))
+                    .fold(None, |patch: Option<Patch>, (_, p)| {
+                        Some(patch.unwrap_or_default().merge(*p))
+                    })?;
+                Some((target.path, patch))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::Width;
+    use crate::cells::{Col, Row, Table, TextCell};
+    use crate::data::DataTable;
+    use crate::patch;
+
+    #[test]
+    fn cascade() {
+        let mut data = DataTable::new(&["host", "cpu"]);
+        data.push(&["alpha", "10"]);
+        data.push(&["beta", "200"]);
+        data.push(&["gamma", "3"]);
+
+        let sheet = Stylesheet::new()
+            .rule(
+                Selector::new().role(Role::Body).named("cpu"),
+                patch!(alignment = Right),
+            )
+            .rule(Selector::new().column(1), patch!(alignment = Left))
+            .rule(Selector::new().role(Role::Header), patch!(border = Heavy))
+            .rule(
+                Selector::new().class("body").parity(Parity::Even),
+                patch!(padding = 1),
+            );
+        let mut table = Table::from(data);
+        table.apply_stylesheet(&sheet);
+
+        assert_eq!(
+            vec![
+                "┏━━━━━━┳━━━━━┓",
+                "┃ host ┃cpu  ┃",
+                "┡━━━━━━╇━━━━━┩",
+                "│alpha │   10│",
+                "├──────┼─────┤",
+                "│ beta │ 200 │",
+                "├──────┼─────┤",
+                "│gamma │    3│",
+                "└──────┴─────┘",
+            ],
+            table.render()
+        );
+    }
+
+    #[test]
+    fn logical_columns() {
+        let text = |s: &str| -> Box<dyn DrawCell> {
+            Box::new(TextCell::new(s.to_string(), Default::default()))
+        };
+        let header = Row::new(vec![text("a"), text("b")], Default::default());
+        let body = Row::new(vec![text("x"), text("y"), text("z")], Default::default());
+        let mut table = Table::from(Col::new(
+            vec![Box::new(header), Box::new(body)],
+            Default::default(),
+        ));
+
+        let sheet = Stylesheet::new()
+            .rule(Selector::new().column(1), patch!(padding = 1))
+            .rule(Selector::new().named("b"), patch!(border = Heavy));
+        table.apply_stylesheet(&sheet);
+
+        let padding = |path: &[usize]| table.get(path).unwrap().get_base().padding;
+        assert_eq!(
+            vec![0, 1, 1, 0],
+            vec![
+                padding(&[0, 0]),
+                padding(&[0, 1]),
+                padding(&[1, 1]),
+                padding(&[1, 2])
+            ]
+        );
+        let border = |path: &[usize]| table.get(path).unwrap().get_base().border;
+        assert_eq!(Width::Heavy, border(&[1, 1]));
+        assert_eq!(Width::None, border(&[1, 2]));
+    }
+}
@@ -1,9 +1,33 @@
-use super::{DrawCell, Grid};
+use crate::config::{Alignment, Bound, CellConfig, UserProperties};
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use super::cell::increase_to_size;
+#[cfg(feature = "serde")]
+use super::snapshot::{Rect, Snapshot, SnapshotError};
+use super::text_cell::wrap;
+use super::{path, Canvas, Cell, Col, DrawCell, Grid, Stylesheet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum CaptionPosition {
+    Border,
+    Below,
+}
 
 #[derive(Debug)]
 pub struct Table<T: DrawCell> {
     cell: T,
-    grid: Grid,
+    // emptied when the cell tree changes, filled again on the next use
+    grid: OnceLock<Grid>,
+    title: Option<(String, Alignment)>,
+    caption: Option<(String, Alignment, CaptionPosition)>,
 }
 
 impl<T: DrawCell> From<T> for Table<T> {
@@ -12,14 +36,636 @@ impl<T: DrawCell> From<T> for Table<T> {
         let mut grid = Grid::new(cell.get_config());
         cell.fixup_grid(grid.slice_mut());
 
-        Self { cell, grid }
+        Self {
+            cell,
+            grid: OnceLock::from(grid),
+            title: None,
+            caption: None,
+        }
     }
 }
 
 impl<T: DrawCell> Table<T> {
+    pub fn title(mut self, title: &str, alignment: Alignment) -> Self {
+        self.title = Some((title.to_string(), alignment));
+        self
+    }
+
+    pub fn caption(
+        mut self,
+        caption: &str,
+        alignment: Alignment,
+        position: CaptionPosition,
+    ) -> Self {
+        self.caption = Some((caption.to_string(), alignment, position));
+        self
+    }
+
+    fn grid(&self) -> &Grid {
+        self.grid.get_or_init(|| {
+            let mut grid = Grid::new(self.cell.get_config());
+            self.cell.fixup_grid(grid.slice_mut());
+            grid
+        })
+    }
+
+    // config fixups are cheap and needed for addressing, the grid waits for the next render
+    fn relayout(&mut self) {
+        self.cell.reset_config();
+        self.cell.fixup_config(1, 1);
+        self.grid.take();
+    }
+
+    pub fn column_widths(&self) -> Vec<usize> {
+        self.grid().widths.clone()
+    }
+
+    pub fn row_heights(&self) -> Vec<usize> {
+        self.grid().heights.clone()
+    }
+
+    // widths are in grid units, each unit also carries one border character;
+    // only text wraps to a new width, so columns with other leaves are left alone,
+    // and the rows grow when the text wraps over more lines
+    pub fn resize_column(&mut self, index: usize, width: usize) -> bool {
+        let rows = self.grid().heights.len();
+        if index >= self.grid().widths.len() {
+            return false;
+        }
+        let text = (0..rows).all(|row| {
+            self.get_at(row, index)
+                .is_some_and(|cell| cell.text().is_some())
+        });
+        if !text {
+            return false;
+        }
+        let grid = match self.grid.get_mut() {
+            Some(grid) => grid,
+            None => return true,
+        };
+        grid.widths[index] = width.max(2);
+        for placed in path::placed(&self.cell) {
+            let (row, col, height, span) = placed.rect;
+            let text = match placed.cell.text() {
+                Some(text) if placed.cell.children().is_empty() => text,
+                _ => continue,
+            };
+            if !(col..col + span).contains(&index) {
+                continue;
+            }
+            let width = grid.widths[col..col + span].iter().sum::<usize>() - 1;
+            let config = placed.cell.get_config();
+            let lines = wrap(text, width, config.padding).len();
+            increase_to_size(&mut grid.heights[row..row + height], lines + 1);
+        }
+        true
+    }
+
+    pub fn get(&self, path: &[usize]) -> Option<&dyn DrawCell> {
+        path::find(&self.cell, path)
+    }
+
+    // path to the cell covering the (row, col) unit of the grid
+    pub fn locate(&self, row: usize, col: usize) -> Option<Vec<usize>> {
+        path::locate(&self.cell, row, col)
+    }
+
+    pub(super) fn title_text(&self) -> Option<&str> {
+        self.title.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    pub(super) fn placed(&self) -> Vec<path::Placed<'_>> {
+        path::placed(&self.cell)
+    }
+
+    pub fn get_at(&self, row: usize, col: usize) -> Option<&dyn DrawCell> {
+        self.get(&self.locate(row, col)?)
+    }
+
+    pub fn text(&self, path: &[usize]) -> Option<&str> {
+        self.get(path)?.text()
+    }
+
+    pub fn update<F: FnOnce(&mut dyn DrawCell)>(&mut self, path: &[usize], f: F) -> bool {
+        match path::find_mut(&mut self.cell, path) {
+            Some(cell) => f(cell),
+            None => return false,
+        }
+        self.relayout();
+        true
+    }
+
+    pub fn set_text(&mut self, path: &[usize], text: &str) -> bool {
+        let mut done = false;
+        self.update(path, |cell| done = cell.set_text(text.to_string())) && done
+    }
+
+    pub fn set_properties(&mut self, path: &[usize], props: UserProperties) -> bool {
+        self.update(path, |cell| *cell.get_base_mut() = CellConfig::from(props))
+    }
+
+    // patches the user given configs of the matched cells, then lays the tree out again
+    pub fn apply_stylesheet(&mut self, sheet: &Stylesheet) {
+        // selectors see the cells as built, not the stretched grid
+        self.cell.reset_config();
+        for (path, patch) in sheet.resolve(&self.cell) {
+            if let Some(cell) = path::find_mut(&mut self.cell, &path) {
+                patch.apply(cell.get_base_mut());
+            }
+        }
+        self.relayout();
+    }
+
+    // the last index of the path is the position of the new cell in its parent
+    pub fn insert(&mut self, path: &[usize], cell: Box<dyn DrawCell>) -> bool {
+        let (&index, parent) = match path.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        let children = match path::find_mut(&mut self.cell, parent) {
+            Some(parent) => parent.children_mut(),
+            None => None,
+        };
+        match children {
+            Some(children) if index <= children.len() => children.insert(index, cell),
+            _ => return false,
+        }
+        self.relayout();
+        true
+    }
+
+    pub fn remove(&mut self, path: &[usize]) -> Option<Box<dyn DrawCell>> {
+        let (&index, parent) = path.split_last()?;
+        let children = path::find_mut(&mut self.cell, parent)?.children_mut()?;
+        // a container without children has nothing to lay out
+        if index >= children.len() || children.len() == 1 {
+            return None;
+        }
+        let cell = children.remove(index);
+        self.relayout();
+        Some(cell)
+    }
+
+    pub fn size(&self) -> Bound {
+        let bound = self.grid().slice().get_bound();
+        let below = match &self.caption {
+            Some((caption, _, CaptionPosition::Below)) => {
+                textwrap::wrap(caption, bound.pt_width + 2).len()
+            }
+            _ => 0,
+        };
+        Bound::new(bound.pt_height + 2 + below, bound.pt_width + 2)
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        self.canvas().render()
+    }
+
+    // everything render shows, the caption below the table included
+    pub fn canvas(&self) -> Canvas {
+        let grid = self.grid();
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.slice().get_bound();
+
+        let below = self.below(true);
+        let mut canvas = Canvas::new(pt_height + 2 + below.len(), pt_width + 2);
+        self.cell.paint(grid.slice(), &mut canvas, 0, 0);
+        self.frame(&mut canvas, pt_height + 1, &below, true);
+        canvas
+    }
+
+    // lines of a caption under the table, only the last page of a split table has it
+    fn below(&self, last: bool) -> Vec<String> {
+        match self.caption.as_ref().filter(|_| last) {
+            Some((caption, alignment, CaptionPosition::Below)) => {
+                self.wrap_caption(caption, *alignment)
+            }
+            _ => vec![],
+        }
+    }
+
+    // title and caption on the border, the lines below go under the bottom line
+    fn frame(&self, canvas: &mut Canvas, bottom: usize, below: &[String], last: bool) {
+        if let Some((title, alignment)) = &self.title {
+            canvas.label(0, title, *alignment);
+        }
+        match self.caption.as_ref().filter(|_| last) {
+            Some((caption, alignment, CaptionPosition::Border)) => {
+                canvas.label(bottom, caption, *alignment)
+            }
+            _ => canvas.text(bottom + 1, 0, below),
+        }
+    }
+
+    pub fn render_svg(&self) -> String {
+        self.canvas().svg()
+    }
+
+    fn wrap_caption(&self, caption: &str, alignment: Alignment) -> Vec<String> {
+        let width = self.grid().slice().get_bound().pt_width + 2;
+        textwrap::wrap(caption, width)
+            .into_iter()
+            .map(|line| console::pad_str(&line, width, alignment.console(false), None).into_owned())
+            .collect()
+    }
+
     pub fn print(&self) {
-        for line in self.cell.draw(self.grid.slice()).complete() {
+        for line in self.render() {
             println!("{}", line)
         }
     }
+
+    // goes through $PAGER when the table does not fit into the terminal
+    pub fn print_paged(&self) {
+        let lines = self.render();
+        let term = console::Term::stdout();
+        if term.is_term() && lines.len() > term.size().0 as usize && page(&lines).is_ok() {
+            return;
+        }
+
+        for line in lines {
+            println!("{}", line)
+        }
+    }
+}
+
+fn page(lines: &[String]) -> std::io::Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let mut args = pager.split_whitespace();
+    let program = args.next().unwrap_or("less");
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        for line in lines {
+            if writeln!(stdin, "{}", line).is_err() {
+                // the pager has been closed before reading everything
+                break;
+            }
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+impl Table<Col> {
+    pub fn insert_row(&mut self, index: usize, row: Box<dyn DrawCell>) -> bool {
+        self.insert(&[index], row)
+    }
+
+    pub fn remove_row(&mut self, index: usize) -> Option<Box<dyn DrawCell>> {
+        self.remove(&[index])
+    }
+
+    // one cell per row, rows that cannot take it are skipped
+    pub fn insert_column(&mut self, index: usize, cells: Vec<Box<dyn DrawCell>>) {
+        for (row, cell) in self.cell.children_mut().unwrap().iter_mut().zip(cells) {
+            if let Some(children) = row.children_mut() {
+                children.insert(index.min(children.len()), cell);
+            }
+        }
+        self.relayout();
+    }
+
+    // nothing is removed when that would leave a row empty
+    pub fn remove_column(&mut self, index: usize) -> bool {
+        let rows = self.cell.children_mut().unwrap();
+        let emptied = rows.iter_mut().any(|row| {
+            row.children_mut()
+                .is_some_and(|children| index < children.len() && children.len() == 1)
+        });
+        if emptied {
+            return false;
+        }
+        for row in rows.iter_mut() {
+            if let Some(children) = row.children_mut() {
+                if index < children.len() {
+                    children.remove(index);
+                }
+            }
+        }
+        self.relayout();
+        true
+    }
+
+    pub fn pages(&self, height: usize) -> Pages<'_> {
+        self.pages_with_header(height, 0)
+    }
+
+    // every page repeats the first header_rows rows and gets its own closed border
+    pub fn pages_with_header(&self, height: usize, header_rows: usize) -> Pages<'_> {
+        let header_rows = header_rows.min(self.cell.len());
+        Pages {
+            table: self,
+            height,
+            header_rows,
+            next: header_rows,
+            started: false,
+        }
+    }
+
+    fn page(&self, rows: &[usize], last: bool) -> Vec<String> {
+        let grid = self.grid().slice();
+        let height: usize = rows
+            .iter()
+            .map(|&i| self.cell.row_height(&grid, i) + 1)
+            .sum();
+        let below = self.below(last);
+        let mut canvas = Canvas::new(height + 1 + below.len(), grid.get_bound().pt_width + 2);
+        self.cell.paint_rows(grid, rows, &mut canvas, 0, 0);
+        self.frame(&mut canvas, height, &below, last);
+        canvas.render()
+    }
+}
+
+// Pages of a table, each one is laid out and painted only when it is reached
+pub struct Pages<'a> {
+    table: &'a Table<Col>,
+    height: usize,
+    header_rows: usize,
+    next: usize,
+    started: bool,
+}
+
+impl Iterator for Pages<'_> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Vec<String>> {
+        let table = self.table;
+        let count = table.cell.len();
+        if self.started && self.next >= count {
+            return None;
+        }
+        self.started = true;
+
+        let grid = table.grid().slice();
+        let mut rows: Vec<usize> = (0..self.header_rows).collect();
+        let mut used = 1 + rows
+            .iter()
+            .map(|&i| table.cell.row_height(&grid, i) + 1)
+            .sum::<usize>();
+        while self.next < count {
+            let row = table.cell.row_height(&grid, self.next) + 1;
+            if rows.len() > self.header_rows && used + row > self.height {
+                break;
+            }
+            used += row;
+            rows.push(self.next);
+            self.next += 1;
+        }
+        if rows.is_empty() {
+            return None;
+        }
+        Some(table.page(&rows, self.next == count))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: DrawCell> Table<T> {
+    // fails if the tree holds cells that cannot be serialised
+    pub fn snapshot(&self) -> Result<Snapshot, SnapshotError> {
+        let config = self.cell.get_config();
+        let rect = Rect {
+            row: 0,
+            col: 0,
+            height: config.span_height,
+            width: config.span_width,
+        };
+        Ok(Snapshot {
+            grid: self.grid().clone(),
+            root: self.cell.describe(rect)?,
+            title: self.title.clone(),
+            caption: self.caption.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Snapshot> for Table<Box<dyn DrawCell>> {
+    fn from(snapshot: Snapshot) -> Self {
+        let mut table = Table::from(snapshot.root.into_cell());
+        table.title = snapshot.title;
+        table.caption = snapshot.caption;
+
+        // keep columns resized by hand, unless the tree no longer fits the saved grid
+        let grid = table.grid.get_mut().unwrap();
+        let fits = |saved: &[usize], new: &[usize]| saved.len() == new.len();
+        if fits(&snapshot.grid.heights, &grid.heights) && fits(&snapshot.grid.widths, &grid.widths)
+        {
+            *grid = snapshot.grid;
+        }
+        table
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: DrawCell> serde::Serialize for Table<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.snapshot() {
+            Ok(snapshot) => snapshot.serialize(serializer),
+            Err(e) => Err(serde::ser::Error::custom(e)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Table<Box<dyn DrawCell>> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Snapshot::deserialize(deserializer).map(Table::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::{Content, ContentCell, Draw, GaugeCell, Row, TextCell};
+    use crate::data::DataTable;
+
+    #[test]
+    fn resize_only_text_columns() {
+        let cols: Vec<Box<dyn DrawCell>> = vec![
+            Box::new(TextCell::new("load".to_string(), Default::default())),
+            Box::new(GaugeCell::new(0.5, Default::default())),
+        ];
+        let mut table = Table::from(Row::new(cols, Default::default()));
+        let widths = table.column_widths();
+
+        assert!(!table.resize_column(1, 3));
+        assert!(!table.resize_column(2, 3));
+        assert_eq!(widths, table.column_widths());
+
+        assert!(table.resize_column(0, 3));
+        assert_eq!(3, table.column_widths()[0]);
+    }
+
+    #[test]
+    fn narrowing_wraps_into_taller_rows() {
+        let light = CellConfig::from(UserProperties {
+            border: crate::borders::Width::Light,
+            ..UserProperties::default()
+        });
+        let cols: Vec<Box<dyn DrawCell>> = vec![
+            Box::new(TextCell::new("one two".to_string(), light)),
+            Box::new(TextCell::new("x".to_string(), light)),
+        ];
+        let mut table = Table::from(Row::new(cols, light));
+        assert_eq!(vec![2], table.row_heights());
+
+        assert!(table.resize_column(0, 4));
+        assert_eq!(vec![3], table.row_heights());
+        assert_eq!(
+            vec!["┌───┬─┐", "│one│x│", "│two│ │", "└───┴─┘"],
+            table.render()
+        );
+    }
+
+    #[test]
+    fn pages_with_header() {
+        let mut data = DataTable::new(&["n"]);
+        for i in 1..=5 {
+            data.push(&[i]);
+        }
+        let table = Table::from(data);
+
+        let pages: Vec<Vec<String>> = table.pages_with_header(7, 1).collect();
+        assert_eq!(
+            vec![
+                vec!["┌─┐", "│n│", "├─┤", "│1│", "├─┤", "│2│", "└─┘"],
+                vec!["┌─┐", "│n│", "├─┤", "│3│", "├─┤", "│4│", "└─┘"],
+                vec!["┌─┐", "│n│", "├─┤", "│5│", "└─┘"],
+            ],
+            pages
+        );
+        assert_eq!(2, table.pages(11).count());
+
+        // the title is on every page, the caption only on the last one
+        let mut data = DataTable::new(&["num"]);
+        for i in 1..=5 {
+            data.push(&[i]);
+        }
+        let table = Table::from(data).title("t", Alignment::Left).caption(
+            "end",
+            Alignment::Left,
+            CaptionPosition::Below,
+        );
+        let mut pages = table.pages_with_header(7, 1);
+        assert_eq!(
+            vec![
+                "┌ t ┐",
+                "│num│",
+                "├───┤",
+                "│ 1 │",
+                "├───┤",
+                "│ 2 │",
+                "└───┘"
+            ],
+            pages.next().unwrap()
+        );
+        assert_eq!(
+            vec!["┌ t ┐", "│num│", "├───┤", "│ 5 │", "└───┘", "end  "],
+            pages.nth(1).unwrap()
+        );
+        assert_eq!(None, pages.next());
+    }
+
+    // with the rayon feature paint goes parallel, draw and pages stay sequential
+    #[test]
+    fn drawing_order() {
+        let mut data = DataTable::new(&["n", "square"]);
+        for i in 1..=50 {
+            data.push(&[i, i * i]);
+        }
+        let table = Table::from(data);
+
+        let sequential = table.pages(1000).next().unwrap();
+        assert_eq!(sequential, table.cell.draw(table.grid().slice()).complete());
+        assert_eq!(sequential, table.render());
+        #[cfg(feature = "rayon")]
+        assert!(table.cell.parts(table.grid().slice(), 0, 0, &mut vec![]));
+    }
+
+    // cells that are not Sync still go into a table, their rows are painted one by one
+    #[test]
+    fn unsync_cells() {
+        struct Shared(std::rc::Rc<str>);
+
+        impl Content for Shared {
+            fn measure(&self) -> (usize, usize) {
+                (1, self.0.len())
+            }
+
+            fn render(&self, _height: usize, _width: usize) -> Vec<String> {
+                vec![self.0.to_string()]
+            }
+        }
+
+        let rows: Vec<Box<dyn DrawCell>> = vec![
+            Box::new(TextCell::new("text".to_string(), CellConfig::default())),
+            Box::new(ContentCell::new(
+                Shared(std::rc::Rc::from("rc")),
+                CellConfig::default(),
+            )),
+        ];
+        let table = Table::from(Col::new(rows, CellConfig::default()));
+        assert_eq!(
+            vec!["      ", " text ", "      ", " rc   ", "      "],
+            table.render()
+        );
+    }
+
+    #[test]
+    fn mutation() {
+        let mut data = DataTable::new(&["a", "b"]);
+        data.push(&["1", "2"]);
+        let mut table = Table::from(data);
+
+        assert_eq!(Some("2"), table.text(&[1, 1]));
+        assert_eq!(Some(vec![1, 0]), table.locate(1, 0));
+        assert!(table.set_text(&[1, 1], "wide"));
+        assert!(!table.set_text(&[1], "row"));
+        assert_eq!(
+            vec!["┌─┬────┐", "│a│ b  │", "├─┼────┤", "│1│wide│", "└─┴────┘"],
+            table.render()
+        );
+
+        let mut copy = DataTable::new(&["x", "y"]);
+        copy.push(&["3", "4"]);
+        let row = copy.build().children_mut().unwrap().remove(1);
+        assert!(table.insert_row(1, row));
+        assert!(table.remove_column(0));
+        assert_eq!(
+            vec![
+                "┌────┐",
+                "│ b  │",
+                "├────┤",
+                "│ 4  │",
+                "├────┤",
+                "│wide│",
+                "└────┘"
+            ],
+            table.render()
+        );
+        assert_eq!(
+            Some("wide"),
+            table.get_at(2, 0).and_then(|cell| cell.text())
+        );
+    }
+
+    #[test]
+    fn keep_the_last_child() {
+        let mut data = DataTable::new(&["a", "b"]);
+        data.push(&["1", "2"]);
+        let mut table = Table::from(data);
+
+        assert!(table.remove_column(1));
+        assert!(!table.remove_column(0));
+        assert!(table.remove(&[0, 0]).is_none());
+        assert!(table.remove_row(0).is_some());
+        assert!(table.remove_row(0).is_none());
+        assert_eq!(vec!["┌─┐", "│1│", "└─┘"], table.render());
+    }
 }
@@ -0,0 +1,180 @@
+use crate::{
+    borders::CellBorder,
+    config::{Bound, CellBounds, CellConfig},
+};
+
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError};
+use super::text_cell::{box_align, pad};
+use super::{Cell, CellView, Draw, DrawCell, GridSlice, GridSliceMut, Table};
+
+// Unlike a nested Row/Col, the embedded table keeps its own frame:
+// its borders are rendered as text and never combined with the parent ones.
+#[derive(Debug)]
+pub struct TableCell<T: DrawCell> {
+    table: Table<T>,
+    base: CellConfig,
+    config: CellConfig,
+}
+
+impl<T: DrawCell> TableCell<T> {
+    pub fn new<U: Into<Table<T>>>(table: U, config: CellConfig) -> Self {
+        let mut cell = Self {
+            table: table.into(),
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
+    }
+}
+
+impl<T: DrawCell> Cell for TableCell<T> {
+    fn get_config(&self) -> &CellConfig {
+        &self.config
+    }
+    fn get_config_mut(&mut self) -> &mut CellConfig {
+        &mut self.config
+    }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
+    fn debug_str(&self) -> String {
+        format!(
+            "TableCell {{ base: {:?}, config: {:?} }}",
+            self.base, self.config
+        )
+    }
+    fn reset_config(&mut self) {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = self.table.size();
+        self.config = CellConfig {
+            bounds: self.base.bounds + CellBounds::from_box(pt_height, pt_width, self.base.padding),
+            ..self.base
+        };
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Table {
+            table: Box::new(self.table.snapshot()?),
+            base: self.base,
+            config: self.config,
+            rect,
+        })
+    }
+    fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
+        self.fixup_config_default(row_ratio, col_ratio)
+    }
+    fn fixup_grid(&self, grid: GridSliceMut) {
+        self.fixup_grid_default(grid)
+    }
+}
+
+impl<T: DrawCell> Draw for TableCell<T> {
+    fn draw(&self, grid: GridSlice) -> CellView {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+        let padding = self.config.padding;
+        let width = pt_width.saturating_sub(2 * padding);
+        let alignment = self.config.alignment.console(false);
+
+        let lines = self
+            .table
+            .render()
+            .into_iter()
+            .map(|line| pad(&console::pad_str(&line, width, alignment, None), padding))
+            .collect();
+        let textbox = box_align(lines, pt_height, pt_width)
+            .iter()
+            .map(|line| self.config.style.apply(line))
+            .collect();
+
+        CellView::new(
+            textbox,
+            CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border),
+        )
+    }
+}
+
+#[macro_export]
+macro_rules! tablecell {
+    ({$($i:ident=$e:expr),*}, $x:expr) => {{
+        let config = $crate::config::CellConfig::from(properties!(border = Light, $($i=$e),*));
+
+        $crate::cells::TableCell::new($x, config)
+    }};
+
+    ($x:expr, {$($i:ident=$e:expr),*}) => {{
+        tablecell!({$($i=$e),*}, $x)
+    }};
+
+    ($x:expr) => {{
+        tablecell!({}, $x)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::Width;
+    use crate::cells::{GaugeCell, Row, SparkCell, TextCell};
+    use crate::config::{Style, UserProperties};
+
+    fn config(border: Width) -> CellConfig {
+        CellConfig::from(UserProperties {
+            border,
+            ..UserProperties::default()
+        })
+    }
+
+    fn text(text: &str) -> Box<dyn DrawCell> {
+        Box::new(TextCell::new(text.to_string(), config(Width::Light)))
+    }
+
+    #[test]
+    fn keeps_own_frame() {
+        let inner = Row::new(vec![text("a"), text("b")], config(Width::Heavy));
+        let cell = TableCell::new(inner, config(Width::Light));
+        let table = Table::from(Row::new(
+            vec![text("x"), Box::new(cell)],
+            config(Width::None),
+        ));
+        assert_eq!(
+            vec![
+                "┌─┬─────┐",
+                "│ │┏━┯━┓│",
+                "│x│┃a│b┃│",
+                "│ │┗━┷━┛│",
+                "└─┴─────┘"
+            ],
+            table.render()
+        );
+    }
+
+    #[test]
+    fn styled_built_in_cells() {
+        console::set_colors_enabled(true);
+        let bold = CellConfig::from(UserProperties {
+            border: Width::Light,
+            style: Style::new().bold(),
+            ..UserProperties::default()
+        });
+        let inner = Row::new(vec![text("in")], config(Width::Light));
+        let cells: Vec<Box<dyn DrawCell>> = vec![
+            Box::new(GaugeCell::new(0.5, bold)),
+            Box::new(SparkCell::new(&[1, 2], bold)),
+            Box::new(TableCell::new(inner, bold)),
+        ];
+        let table = Table::from(Row::new(cells, config(Width::Light)));
+
+        let line = &table.render()[1];
+        assert_eq!(3, line.matches("\x1b[1m").count(), "{:?}", line);
+    }
+}
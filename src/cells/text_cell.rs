@@ -1,27 +1,60 @@
+use std::borrow::Cow;
+
 use crate::{
     borders::CellBorder,
-    config::{Alignment, Bound, CellBounds, CellConfig},
+    config::{Bound, CellBounds, CellConfig},
 };
 
-use super::{Cell, CellView, Draw, GridSlice, GridSliceMut};
+#[cfg(feature = "rayon")]
+use super::cell::parts::{self as cell_parts, Part};
+#[cfg(feature = "serde")]
+use super::snapshot::{Node, Rect, SnapshotError};
+use super::{Canvas, Cell, CellView, Draw, GridSlice, GridSliceMut};
 
 #[derive(Debug)]
 pub struct TextCell {
     text: String,
+    base: CellConfig,
     config: CellConfig,
 }
 
 impl TextCell {
     pub fn new(text: String, config: CellConfig) -> Self {
         // fuck your tabs! probably
-        let text = text.replace('\t', " ");
-        Self {
+        let mut cell = Self {
             text: text.replace('\t', " "),
-            config: CellConfig {
-                bounds: config.bounds + CellBounds::from_text(&text, config.padding),
-                ..config
-            },
-        }
+            base: config,
+            config,
+        };
+        cell.reset_config();
+        cell
+    }
+
+    // (row, column, line) of every wrapped line that fits the box, placed the way
+    // box_align and the alignment would place them; draw and paint both go by it
+    fn layout(&self, height: usize, width: usize) -> Vec<(usize, usize, Cow<'_, str>)> {
+        let padding = self.config.padding;
+        let inner = width.saturating_sub(2 * padding).max(1);
+        let multiline = console::measure_text_width(&self.text) > inner;
+        let alignment = self.config.alignment.console(multiline);
+        let lines = wrap(&self.text, width, padding);
+        let above = height.saturating_sub(lines.len()) / 2;
+
+        lines
+            .into_iter()
+            .take(height)
+            .enumerate()
+            .map(|(y, line)| {
+                let padded = console::measure_text_width(&line) + 2 * padding;
+                let x = match alignment {
+                    _ if padded >= width => 0,
+                    console::Alignment::Left => 0,
+                    console::Alignment::Center => (width - padded) / 2,
+                    console::Alignment::Right => width - padded,
+                };
+                (above + y, x + padding, line)
+            })
+            .collect()
     }
 }
 
@@ -32,9 +65,47 @@ impl Cell for TextCell {
     fn get_config_mut(&mut self) -> &mut CellConfig {
         &mut self.config
     }
+    fn get_base(&self) -> &CellConfig {
+        &self.base
+    }
+    fn get_base_mut(&mut self) -> &mut CellConfig {
+        &mut self.base
+    }
     fn debug_str(&self) -> String {
         format!("{:?}", self)
     }
+    fn reset_config(&mut self) {
+        self.config = CellConfig {
+            bounds: self.base.bounds + CellBounds::from_text(&self.text, self.base.padding),
+            ..self.base
+        };
+    }
+    fn text(&self) -> Option<&str> {
+        Some(&self.text)
+    }
+    fn set_text(&mut self, text: String) -> bool {
+        self.text = text.replace('\t', " ");
+        true
+    }
+    #[cfg(feature = "serde")]
+    fn describe(&self, rect: Rect) -> Result<Node, SnapshotError> {
+        Ok(Node::Text {
+            text: self.text.clone(),
+            base: self.base,
+            config: self.config,
+            rect,
+        })
+    }
+    #[cfg(feature = "rayon")]
+    fn parts<'a>(
+        &'a self,
+        grid: GridSlice<'a>,
+        top: usize,
+        left: usize,
+        parts: &mut Vec<Part<'a>>,
+    ) -> bool {
+        cell_parts::leaf(self, grid, top, left, parts)
+    }
     fn fixup_config(&mut self, row_ratio: usize, col_ratio: usize) {
         self.fixup_config_default(row_ratio, col_ratio)
     }
@@ -43,27 +114,17 @@ impl Cell for TextCell {
     }
 }
 
-fn pad(text: &str, padding: usize) -> String {
+pub(super) fn pad(text: &str, padding: usize) -> String {
     " ".repeat(padding) + text + &" ".repeat(padding)
 }
 
-fn wrap(text: &str, width: usize, padding: usize, alignment: Alignment) -> Vec<String> {
-    if width == 0 {
-        text.lines().map(|s| pad(s, padding)).collect()
-    } else {
-        // TODO there are at least two fatal flaws in this code!
-        let multiline = console::measure_text_width(text) > width - 2 * padding;
-        textwrap::wrap(text, width - 2 * padding)
-            .into_iter()
-            .map(|s| {
-                console::pad_str(&pad(&s, padding), width, alignment.console(multiline), None)
-                    .into_owned()
-            })
-            .collect()
-    }
+// the text broken into lines that fit the box inside the padding;
+// the grid may be narrower than requested (e.g. resized by hand), never overflow it
+pub(super) fn wrap(text: &str, width: usize, padding: usize) -> Vec<Cow<'_, str>> {
+    textwrap::wrap(text, width.saturating_sub(2 * padding).max(1))
 }
 
-fn box_align(text: Vec<String>, box_height: usize, box_width: usize) -> Vec<String> {
+pub(super) fn box_align(text: Vec<String>, box_height: usize, box_width: usize) -> Vec<String> {
     let text_height = text.len();
     if text_height >= box_height {
         text.into_iter().take(box_height).collect()
@@ -83,19 +144,40 @@ impl Draw for TextCell {
             pt_height,
             pt_width,
         } = grid.get_bound();
-        let wrapped_text = wrap(
-            &self.text,
-            pt_width,
-            self.config.padding,
-            self.config.alignment,
-        );
-        let textbox = box_align(wrapped_text, pt_height, pt_width);
+        let mut textbox = vec![" ".repeat(pt_width); pt_height];
+        for (y, x, line) in self.layout(pt_height, pt_width) {
+            let line = " ".repeat(x) + &line;
+            textbox[y] =
+                console::pad_str(&line, pt_width, console::Alignment::Left, Some("")).into_owned();
+        }
+        let textbox = textbox
+            .iter()
+            .map(|line| self.config.style.apply(line))
+            .collect();
 
         CellView::new(
             textbox,
             CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border),
         )
     }
+
+    // plain text is put right into the canvas, without building padded lines;
+    // styles and escapes take the way through draw
+    fn paint(&self, grid: GridSlice, canvas: &mut Canvas, top: usize, left: usize) {
+        let Bound {
+            pt_height,
+            pt_width,
+        } = grid.get_bound();
+        if !self.config.style.is_plain() || self.text.contains('\x1b') {
+            return self.draw(grid).paint(canvas, top, left);
+        }
+
+        for (y, x, line) in self.layout(pt_height, pt_width) {
+            canvas.plain(top + 1 + y, left + 1 + x, &line, left + 1 + pt_width);
+        }
+        let border = CellBorder::atomic(pt_height + 2, pt_width + 2, self.config.border);
+        canvas.border(top, left, &border);
+    }
 }
 
 #[macro_export]
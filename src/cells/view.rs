@@ -1,5 +1,7 @@
 use crate::borders::CellBorder;
 
+use super::Canvas;
+
 pub struct CellView {
     textbox: Vec<String>,
     border: CellBorder,
@@ -22,6 +24,11 @@ impl CellView {
         (self.textbox, self.border)
     }
 
+    pub fn paint(self, canvas: &mut Canvas, top: usize, left: usize) {
+        canvas.text(top + 1, left + 1, &self.textbox);
+        canvas.border(top, left, &self.border);
+    }
+
     pub fn complete(self) -> Vec<String> {
         self.border.render_view(&self.textbox)
     }
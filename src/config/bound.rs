@@ -4,6 +4,7 @@ use std::ops::{Add, AddAssign};
 use crate::config::utils::*;
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bound {
     pub pt_height: usize,
     pub pt_width: usize,
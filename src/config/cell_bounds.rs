@@ -5,6 +5,7 @@ use crate::config::utils::*;
 use crate::config::Bound;
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellBounds {
     pub min: Bound,
     pub rec: Bound,
@@ -22,9 +23,18 @@ impl CellBounds {
     pub fn from_text(text: &str, padding: usize) -> Self {
         Self::new(
             Bound::new(2, 2 + 2 * padding),
-            Bound::new(2, text.len() + 1 + 2 * padding),
+            Bound::new(2, console::measure_text_width(text) + 1 + 2 * padding),
         )
     }
+
+    pub fn from_width(width: usize, padding: usize) -> Self {
+        Self::from_box(1, width, padding)
+    }
+
+    pub fn from_box(height: usize, width: usize, padding: usize) -> Self {
+        let bound = Bound::new(height + 1, width + 1 + 2 * padding);
+        Self::new(bound, bound)
+    }
 }
 
 impl FromIterator<CellBounds> for Vertical<CellBounds> {
@@ -2,13 +2,15 @@ use std::iter::FromIterator;
 
 use crate::borders::Width;
 use crate::config::utils::*;
-use crate::config::{Alignment, Bound, CellBounds, UserProperties};
+use crate::config::{Alignment, Bound, CellBounds, Style, UserProperties};
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellConfig {
     pub border: Width,
     pub alignment: Alignment,
     pub padding: usize,
+    pub style: Style,
 
     pub bounds: CellBounds,
     pub span_height: usize,
@@ -21,6 +23,7 @@ impl From<UserProperties> for CellConfig {
             border: props.border,
             alignment: props.alignment,
             padding: props.padding,
+            style: props.style,
 
             bounds: CellBounds::new(
                 Bound::new(2, 2),
@@ -0,0 +1,46 @@
+use std::{fmt, fs, io, path::Path};
+
+use serde::de::DeserializeOwned;
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "cannot read config: {}", e),
+            LoadError::Toml(e) => write!(f, "invalid TOML config: {}", e),
+            LoadError::Json(e) => write!(f, "invalid JSON config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+pub fn from_toml<T: DeserializeOwned>(text: &str) -> Result<T, LoadError> {
+    toml::from_str(text).map_err(LoadError::Toml)
+}
+
+pub fn from_json<T: DeserializeOwned>(text: &str) -> Result<T, LoadError> {
+    serde_json::from_str(text).map_err(LoadError::Json)
+}
+
+// the format is picked by extension, anything but .json is read as TOML
+pub fn load<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, LoadError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => from_json(&text),
+        _ => from_toml(&text),
+    }
+}
@@ -11,5 +11,17 @@ pub use bound::Bound;
 mod cell_bounds;
 pub use cell_bounds::CellBounds;
 
+mod style;
+#[cfg(feature = "serde")]
+pub(crate) use style::color;
+pub use style::Style;
+
+#[macro_use]
+mod patch;
+pub use patch::Patch;
+
 mod cell_config;
 pub use cell_config::CellConfig;
+
+#[cfg(feature = "serde")]
+pub mod file;
@@ -0,0 +1,83 @@
+use crate::borders::Width;
+use crate::config::{Alignment, CellConfig, Style};
+
+// A partial UserProperties: only the fields that are set get applied.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Patch {
+    pub border: Option<Width>,
+    pub alignment: Option<Alignment>,
+    pub padding: Option<usize>,
+    pub style: Option<Style>,
+
+    pub pt_height: Option<usize>,
+    pub pt_width: Option<usize>,
+
+    pub span_height: Option<usize>,
+    pub span_width: Option<usize>,
+}
+
+impl Patch {
+    // fields set in the other patch win
+    pub fn merge(self, other: Patch) -> Self {
+        Self {
+            border: other.border.or(self.border),
+            alignment: other.alignment.or(self.alignment),
+            padding: other.padding.or(self.padding),
+            style: other.style.or(self.style),
+
+            pt_height: other.pt_height.or(self.pt_height),
+            pt_width: other.pt_width.or(self.pt_width),
+
+            span_height: other.span_height.or(self.span_height),
+            span_width: other.span_width.or(self.span_width),
+        }
+    }
+
+    pub fn apply(&self, config: &mut CellConfig) {
+        if let Some(border) = self.border {
+            config.border = border;
+        }
+        if let Some(alignment) = self.alignment {
+            config.alignment = alignment;
+        }
+        if let Some(padding) = self.padding {
+            config.padding = padding;
+        }
+        if let Some(style) = self.style {
+            config.style = style;
+        }
+
+        // same as in CellConfig::from(UserProperties)
+        if let Some(pt_height) = self.pt_height {
+            config.bounds.rec.pt_height = pt_height + 1;
+        }
+        if let Some(pt_width) = self.pt_width {
+            config.bounds.rec.pt_width = pt_width + 1;
+        }
+
+        if let Some(span_height) = self.span_height {
+            config.span_height = span_height;
+        }
+        if let Some(span_width) = self.span_width {
+            config.span_width = span_width;
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! patch {
+    ($($i:ident=$e:expr),* $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::config::Alignment::*;
+        #[allow(unused_imports)]
+        use $crate::borders::Width::*;
+        let mut patch = $crate::config::Patch::default();
+        $(patch.$i = Some($e);)*
+        patch
+    }};
+}
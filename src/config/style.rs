@@ -0,0 +1,151 @@
+use console::Color;
+
+// Text attributes of a cell. Unlike console::Style it is Copy
+// and two styles can be layered on top of each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Style {
+    #[cfg_attr(feature = "serde", serde(with = "color"))]
+    pub fg: Option<Color>,
+    #[cfg_attr(feature = "serde", serde(with = "color"))]
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underlined: bool,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub fn underlined(mut self) -> Self {
+        self.underlined = true;
+        self
+    }
+
+    // colours of the other style win, attributes add up
+    pub fn merge(self, other: Style) -> Self {
+        Self {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underlined: self.underlined || other.underlined,
+        }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn console(&self) -> console::Style {
+        let mut style = console::Style::new();
+        if let Some(color) = self.fg {
+            style = style.fg(color);
+        }
+        if let Some(color) = self.bg {
+            style = style.bg(color);
+        }
+        if self.bold {
+            style = style.bold();
+        }
+        if self.dim {
+            style = style.dim();
+        }
+        if self.italic {
+            style = style.italic();
+        }
+        if self.underlined {
+            style = style.underlined();
+        }
+        style
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        if self.is_plain() {
+            text.to_string()
+        } else {
+            self.console().apply_to(text).to_string()
+        }
+    }
+}
+
+// colours are given by name or by their index in the 256 colour palette
+#[cfg(feature = "serde")]
+pub(crate) mod color {
+    use console::Color;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Name(String),
+        Index(u8),
+    }
+
+    const NAMES: [(&str, Color); 8] = [
+        ("black", Color::Black),
+        ("red", Color::Red),
+        ("green", Color::Green),
+        ("yellow", Color::Yellow),
+        ("blue", Color::Blue),
+        ("magenta", Color::Magenta),
+        ("cyan", Color::Cyan),
+        ("white", Color::White),
+    ];
+
+    pub fn serialize<S: Serializer>(color: &Option<Color>, s: S) -> Result<S::Ok, S::Error> {
+        let repr = color.map(|color| match color {
+            Color::Color256(index) => Repr::Index(index),
+            color => {
+                let (name, _) = NAMES.iter().find(|(_, c)| *c == color).unwrap();
+                Repr::Name(name.to_string())
+            }
+        });
+        repr.serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Color>, D::Error> {
+        let color = match Option::<Repr>::deserialize(d)? {
+            None => return Ok(None),
+            Some(Repr::Index(index)) => Color::Color256(index),
+            Some(Repr::Name(name)) => match NAMES.iter().find(|(n, _)| *n == name) {
+                Some((_, color)) => *color,
+                None => return Err(D::Error::custom(format!("unknown colour `{}`", name))),
+            },
+        };
+        Ok(Some(color))
+    }
+}
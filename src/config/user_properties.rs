@@ -1,6 +1,12 @@
 use crate::borders::Width;
+use crate::config::Style;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Alignment {
     Default,
     Left,
@@ -26,10 +32,16 @@ impl Alignment {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
 pub struct UserProperties {
     pub border: Width,
     pub alignment: Alignment,
     pub padding: usize,
+    pub style: Style,
 
     pub pt_height: usize,
     pub pt_width: usize,
@@ -44,6 +56,7 @@ impl Default for UserProperties {
             border: Width::None,
             alignment: Alignment::Default,
             padding: 0,
+            style: Style::default(),
 
             pt_height: 0,
             pt_width: 0,
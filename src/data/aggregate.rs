@@ -0,0 +1,87 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Aggregate {
+    Sum,
+    #[cfg_attr(feature = "serde", serde(alias = "avg"))]
+    Average,
+    Min,
+    Max,
+    Count,
+}
+
+impl Aggregate {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Average => "avg",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+            Aggregate::Count => "count",
+        }
+    }
+
+    // non-numeric values are skipped, except for Count which counts every non-empty value
+    pub fn apply<'a, I: IntoIterator<Item = &'a str>>(&self, values: I) -> Option<f64> {
+        let values: Vec<&str> = values
+            .into_iter()
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if let Aggregate::Count = self {
+            return Some(values.len() as f64);
+        }
+
+        let numbers: Vec<f64> = values.iter().filter_map(|v| v.parse().ok()).collect();
+        if numbers.is_empty() {
+            return None;
+        }
+
+        Some(match self {
+            Aggregate::Sum => numbers.iter().sum(),
+            Aggregate::Average => numbers.iter().sum::<f64>() / numbers.len() as f64,
+            Aggregate::Min => numbers.iter().cloned().fold(f64::INFINITY, f64::min),
+            Aggregate::Max => numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            Aggregate::Count => unreachable!(),
+        })
+    }
+
+    pub fn format(value: f64) -> String {
+        if value.fract() == 0.0 {
+            format!("{}", value)
+        } else {
+            // rounding may leave no fraction at all, e.g. 2.999
+            let text = format!("{:.2}", value);
+            text.trim_end_matches('0').trim_end_matches('.').to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates() {
+        let values = ["1", "2.5", "", "n/a", " 4 "];
+        assert_eq!(Some(7.5), Aggregate::Sum.apply(values.iter().cloned()));
+        assert_eq!(Some(2.5), Aggregate::Average.apply(values.iter().cloned()));
+        assert_eq!(Some(1.0), Aggregate::Min.apply(values.iter().cloned()));
+        assert_eq!(Some(4.0), Aggregate::Max.apply(values.iter().cloned()));
+        assert_eq!(Some(4.0), Aggregate::Count.apply(values.iter().cloned()));
+        assert_eq!(None, Aggregate::Sum.apply(vec!["n/a"]));
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!("3", Aggregate::format(3.0));
+        assert_eq!("3.5", Aggregate::format(3.5));
+        assert_eq!("3.33", Aggregate::format(10.0 / 3.0));
+        assert_eq!("3", Aggregate::format(3.001));
+        assert_eq!("3", Aggregate::format(2.999));
+        assert_eq!("-3", Aggregate::format(-2.999));
+    }
+}
@@ -0,0 +1,649 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    borders::Width,
+    cells::{ClassCell, Col, DrawCell, Row, RuleCell, Table, TextCell},
+    config::{Alignment, CellConfig, Style, UserProperties},
+};
+
+use super::rule::Rule;
+use super::{Aggregate, SortKey};
+
+// a column index past the last column of the table
+#[derive(Debug, PartialEq)]
+pub struct ColumnError {
+    pub column: usize,
+    pub columns: usize,
+}
+
+impl fmt::Display for ColumnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "column {} is out of range for a table of {} columns",
+            self.column, self.columns
+        )
+    }
+}
+
+impl std::error::Error for ColumnError {}
+
+#[derive(Clone, Debug)]
+pub struct DataTable {
+    headers: Vec<String>,
+    records: Vec<Vec<String>>,
+    footers: Vec<(Aggregate, Vec<usize>)>,
+    footer_rule: Width,
+    group: Option<usize>,
+    subtotals: Vec<(Aggregate, Vec<usize>)>,
+    group_rule: Width,
+    rules: Vec<Rule>,
+
+    header_props: UserProperties,
+    props: UserProperties,
+    footer_props: UserProperties,
+}
+
+// rows and cells are tagged with their role for stylesheets
+pub(super) fn tagged<C: DrawCell + 'static>(cell: C, classes: &[&str]) -> Box<dyn DrawCell> {
+    Box::new(ClassCell::new(Box::new(cell), classes))
+}
+
+pub(super) fn cell_properties() -> UserProperties {
+    UserProperties {
+        border: Width::Light,
+        ..UserProperties::default()
+    }
+}
+
+impl DataTable {
+    pub fn new<S: ToString>(headers: &[S]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            records: vec![],
+            footers: vec![],
+            footer_rule: Width::Heavy,
+            group: None,
+            subtotals: vec![],
+            group_rule: Width::Heavy,
+            rules: vec![],
+
+            header_props: cell_properties(),
+            props: cell_properties(),
+            footer_props: cell_properties(),
+        }
+    }
+
+    pub fn push<S: ToString>(&mut self, record: &[S]) {
+        let mut record: Vec<String> = record.iter().map(|v| v.to_string()).collect();
+        record.resize(self.headers.len(), String::new());
+        self.records.push(record);
+    }
+
+    pub fn header_properties(mut self, props: UserProperties) -> Self {
+        self.header_props = props;
+        self
+    }
+
+    pub fn properties(mut self, props: UserProperties) -> Self {
+        self.props = props;
+        self
+    }
+
+    pub fn footer_properties(mut self, props: UserProperties) -> Self {
+        self.footer_props = props;
+        self
+    }
+
+    // every call adds one footer row aggregating the given columns
+    pub fn footer(mut self, aggregate: Aggregate, columns: &[usize]) -> Self {
+        self.footers.push((aggregate, columns.to_vec()));
+        self
+    }
+
+    pub fn footer_rule(mut self, rule: Width) -> Self {
+        self.footer_rule = rule;
+        self
+    }
+
+    // the key column is moved to the front and printed once per group
+    pub fn group_by(mut self, column: usize) -> Result<Self, ColumnError> {
+        self.check(&[column])?;
+        self.group = Some(column);
+        Ok(self)
+    }
+
+    // every call adds one subtotal row to each group
+    pub fn subtotal(
+        mut self,
+        aggregate: Aggregate,
+        columns: &[usize],
+    ) -> Result<Self, ColumnError> {
+        self.check(columns)?;
+        self.subtotals.push((aggregate, columns.to_vec()));
+        Ok(self)
+    }
+
+    pub fn group_rule(mut self, rule: Width) -> Self {
+        self.group_rule = rule;
+        self
+    }
+
+    fn check(&self, columns: &[usize]) -> Result<(), ColumnError> {
+        match columns.iter().find(|&&column| column >= self.headers.len()) {
+            Some(&column) => Err(ColumnError {
+                column,
+                columns: self.headers.len(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // every other body row, counting from the second one
+    pub fn stripe(mut self, style: Style) -> Self {
+        self.rules.push(Rule::Stripe(style));
+        self
+    }
+
+    // the predicate sees records as they are when the table is built
+    pub fn emphasize<F: Fn(&[String]) -> bool + Send + Sync + 'static>(
+        mut self,
+        predicate: F,
+        style: Style,
+    ) -> Self {
+        self.rules.push(Rule::Row(Arc::new(predicate), style));
+        self
+    }
+
+    // rules are layered in the order they were added
+    pub fn highlight<F: Fn(&str) -> bool + Send + Sync + 'static>(
+        mut self,
+        column: usize,
+        predicate: F,
+        style: Style,
+    ) -> Self {
+        self.rules
+            .push(Rule::Cell(column, Arc::new(predicate), style));
+        self
+    }
+
+    pub fn sort_by(&mut self, keys: &[SortKey]) -> Result<(), ColumnError> {
+        let columns: Vec<usize> = keys.iter().map(|key| key.column).collect();
+        self.check(&columns)?;
+        self.records.sort_by(|a, b| {
+            keys.iter()
+                .map(|key| key.compare(a, b))
+                .find(|ordering| *ordering != std::cmp::Ordering::Equal)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Ok(())
+    }
+
+    pub fn filter<F: Fn(&[String]) -> bool>(&mut self, predicate: F) {
+        self.records.retain(|record| predicate(record));
+    }
+
+    // keeps only the given columns in the given order, footers follow their columns
+    pub fn select(&mut self, columns: &[usize]) -> Result<(), ColumnError> {
+        self.check(columns)?;
+        let project = |values: &[String]| -> Vec<String> {
+            columns.iter().map(|&i| values[i].clone()).collect()
+        };
+        self.headers = project(&self.headers);
+        for record in self.records.iter_mut() {
+            *record = project(record);
+        }
+        self.group = self
+            .group
+            .and_then(|group| columns.iter().position(|&i| i == group));
+        let aggregates = self.footers.iter_mut().chain(self.subtotals.iter_mut());
+        for (_, footer_columns) in aggregates {
+            *footer_columns = columns
+                .iter()
+                .enumerate()
+                .filter(|(_, i)| footer_columns.contains(i))
+                .map(|(new, _)| new)
+                .collect();
+        }
+        // highlights of dropped columns are dropped with them
+        self.rules.retain_mut(|rule| match rule.column_mut() {
+            Some(column) => match columns.iter().position(|i| i == column) {
+                Some(new) => {
+                    *column = new;
+                    true
+                }
+                None => false,
+            },
+            None => true,
+        });
+        Ok(())
+    }
+
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|h| h == name)
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn records(&self) -> &[Vec<String>] {
+        &self.records
+    }
+
+    fn column_order(&self) -> Vec<usize> {
+        let columns = 0..self.headers.len();
+        match self.group {
+            Some(key) => std::iter::once(key)
+                .chain(columns.filter(|&i| i != key))
+                .collect(),
+            None => columns.collect(),
+        }
+    }
+
+    fn groups(&self, key: usize) -> Vec<(&str, Vec<&Vec<String>>)> {
+        let mut groups: Vec<(&str, Vec<&Vec<String>>)> = vec![];
+        for record in self.records.iter() {
+            match groups.iter_mut().find(|(k, _)| *k == record[key]) {
+                Some((_, records)) => records.push(record),
+                None => groups.push((&record[key], vec![record])),
+            }
+        }
+        groups
+    }
+
+    fn aggregate_values(
+        &self,
+        records: &[&Vec<String>],
+        (aggregate, columns): &(Aggregate, Vec<usize>),
+        label: usize,
+    ) -> Vec<String> {
+        (0..self.headers.len())
+            .map(|i| {
+                if columns.contains(&i) {
+                    let values = records.iter().map(|r| r[i].as_str());
+                    aggregate
+                        .apply(values)
+                        .map(Aggregate::format)
+                        .unwrap_or_default()
+                } else if i == label {
+                    aggregate.name().to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .collect()
+    }
+
+    pub(super) fn row(values: &[String], props: UserProperties) -> Row {
+        let cells = values
+            .iter()
+            .map(|v| {
+                let cell: Box<dyn DrawCell> =
+                    Box::new(TextCell::new(v.clone(), CellConfig::from(props)));
+                cell
+            })
+            .collect();
+        Row::new(cells, CellConfig::default())
+    }
+
+    fn record_props(&self, index: usize, record: &[String], column: usize) -> UserProperties {
+        let style = self.rules.iter().fold(self.props.style, |style, rule| {
+            style.merge(rule.style(index, record, column))
+        });
+        UserProperties {
+            style,
+            ..self.props
+        }
+    }
+
+    fn record_row(&self, index: usize, record: &[String], columns: &[usize]) -> Row {
+        let cells = columns
+            .iter()
+            .map(|&i| {
+                let props = self.record_props(index, record, i);
+                let cell: Box<dyn DrawCell> =
+                    Box::new(TextCell::new(record[i].clone(), CellConfig::from(props)));
+                cell
+            })
+            .collect();
+        Row::new(cells, CellConfig::default())
+    }
+
+    fn group_row(
+        &self,
+        key: &str,
+        records: &[&Vec<String>],
+        columns: &[usize],
+        first: usize,
+    ) -> Row {
+        let project = |values: &[String]| -> Vec<String> {
+            columns.iter().map(|&i| values[i].clone()).collect()
+        };
+
+        let mut rows: Vec<Box<dyn DrawCell>> = vec![];
+        for (i, record) in records.iter().enumerate() {
+            rows.push(tagged(
+                self.record_row(first + i, record, columns),
+                &["body"],
+            ));
+        }
+        for subtotal in self.subtotals.iter() {
+            let values = self.aggregate_values(records, subtotal, columns[0]);
+            let row = Self::row(&project(&values), self.footer_props);
+            rows.push(tagged(row, &["footer", "subtotal"]));
+        }
+
+        let key = tagged(
+            TextCell::new(key.to_string(), CellConfig::from(self.props)),
+            &["body", "group"],
+        );
+        let rows: Box<dyn DrawCell> = Box::new(Col::new(rows, CellConfig::default()));
+        Row::new(vec![key, rows], CellConfig::default())
+    }
+
+    pub fn build(&self) -> Col {
+        let order = self.column_order();
+        let project = |values: &[String]| -> Vec<String> {
+            order.iter().map(|&i| values[i].clone()).collect()
+        };
+
+        let mut rows: Vec<Box<dyn DrawCell>> = vec![];
+        let header = Self::row(&project(&self.headers), self.header_props);
+        rows.push(tagged(header, &["header"]));
+
+        match self.group {
+            Some(key) if order.len() > 1 => {
+                let mut first = 0;
+                for (i, (value, records)) in self.groups(key).into_iter().enumerate() {
+                    let row = Box::new(self.group_row(value, &records, &order[1..], first));
+                    first += records.len();
+                    if i == 0 {
+                        rows.push(row);
+                    } else {
+                        rows.push(Box::new(RuleCell::new(row, self.group_rule)));
+                    }
+                }
+            }
+            _ => {
+                for (i, record) in self.records.iter().enumerate() {
+                    rows.push(tagged(self.record_row(i, record, &order), &["body"]));
+                }
+            }
+        }
+
+        let records: Vec<&Vec<String>> = self.records.iter().collect();
+        for (i, footer) in self.footers.iter().enumerate() {
+            let values = self.aggregate_values(&records, footer, order[0]);
+            let row = tagged(Self::row(&project(&values), self.footer_props), &["footer"]);
+            if i == 0 {
+                rows.push(Box::new(RuleCell::new(row, self.footer_rule)));
+            } else {
+                rows.push(row);
+            }
+        }
+
+        Col::new(rows, CellConfig::default())
+    }
+}
+
+impl DataTable {
+    // psql-like \x mode: every record becomes a block of name/value rows
+    pub fn build_expanded(&self) -> Col {
+        let mut rows: Vec<Box<dyn DrawCell>> = vec![];
+        for (i, record) in self.records.iter().enumerate() {
+            let title = UserProperties {
+                alignment: Alignment::Left,
+                span_width: 2,
+                ..self.header_props
+            };
+            rows.push(tagged(
+                TextCell::new(format!("-[ RECORD {} ]-", i + 1), CellConfig::from(title)),
+                &["header", "title"],
+            ));
+
+            for (j, (header, value)) in self.headers.iter().zip(record.iter()).enumerate() {
+                let props = self.record_props(i, record, j);
+                let cells = vec![
+                    tagged(
+                        TextCell::new(header.clone(), CellConfig::from(self.header_props)),
+                        &["header"],
+                    ),
+                    tagged(
+                        TextCell::new(value.clone(), CellConfig::from(props)),
+                        &["body"],
+                    ),
+                ];
+                rows.push(Box::new(Row::new(cells, CellConfig::default())));
+            }
+        }
+        // an empty result is still a table, like the (0 rows) of psql
+        if rows.is_empty() {
+            let props = UserProperties {
+                span_width: 2,
+                ..self.props
+            };
+            rows.push(tagged(
+                TextCell::new("(0 rows)".to_string(), CellConfig::from(props)),
+                &["body", "empty"],
+            ));
+        }
+
+        Col::new(rows, CellConfig::default())
+    }
+
+    // falls back to the expanded mode when the regular table is wider than max_width
+    pub fn fit(&self, max_width: usize) -> Table<Col> {
+        let table = Table::from(self.build());
+        if table.size().pt_width > max_width && !self.records.is_empty() {
+            Table::from(self.build_expanded())
+        } else {
+            table
+        }
+    }
+
+    pub fn print(&self) {
+        let term = console::Term::stdout();
+        let table = if term.is_term() {
+            self.fit(term.size().1 as usize)
+        } else {
+            Table::from(self.build())
+        };
+        table.print();
+    }
+}
+
+impl From<&DataTable> for Table<Col> {
+    fn from(data: &DataTable) -> Self {
+        Table::from(data.build())
+    }
+}
+
+impl From<DataTable> for Table<Col> {
+    fn from(data: DataTable) -> Self {
+        Table::from(data.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Comparator, Order};
+
+    #[test]
+    fn footer_rows() {
+        let mut data = DataTable::new(&["host", "cpu"])
+            .footer(Aggregate::Sum, &[1])
+            .footer(Aggregate::Max, &[1]);
+        data.push(&["alpha", "10"]);
+        data.push(&["beta", "200"]);
+
+        assert_eq!(
+            vec![
+                "┌─────┬───┐",
+                "│host │cpu│",
+                "├─────┼───┤",
+                "│alpha│10 │",
+                "├─────┼───┤",
+                "│beta │200│",
+                "┝━━━━━┿━━━┥",
+                "│ sum │210│",
+                "├─────┼───┤",
+                "│ max │200│",
+                "└─────┴───┘",
+            ],
+            Table::from(data).render()
+        );
+    }
+
+    #[test]
+    fn reshape() {
+        let mut data = DataTable::new(&["host", "cpu", "mem"]).footer(Aggregate::Sum, &[1]);
+        data.push(&["host10", "5", "1"]);
+        data.push(&["host9", "5", "2"]);
+        data.push(&["host2", "30", "3"]);
+        data.push(&["host1", "1", "4"]);
+
+        data.filter(|record| record[2] != "4");
+        data.sort_by(&[
+            SortKey::new(1, Comparator::Numeric, Order::Descending),
+            SortKey::new(0, Comparator::Natural, Order::Ascending),
+        ])
+        .unwrap();
+        data.select(&[1, 0]).unwrap();
+
+        assert_eq!(&["cpu", "host"], data.headers());
+        assert_eq!(
+            vec![vec!["30", "host2"], vec!["5", "host9"], vec!["5", "host10"]],
+            data.records().to_vec()
+        );
+        assert_eq!(vec![0], data.footers[0].1);
+
+        let error = ColumnError {
+            column: 2,
+            columns: 2,
+        };
+        assert_eq!(Err(error), data.select(&[0, 2]));
+        let key = SortKey::new(5, Comparator::Numeric, Order::Ascending);
+        assert!(data.sort_by(&[key]).is_err());
+        assert_eq!(&["cpu", "host"], data.headers());
+    }
+
+    #[test]
+    fn groups() {
+        let mut data = DataTable::new(&["disk", "host", "used"])
+            .group_by(1)
+            .and_then(|data| data.subtotal(Aggregate::Sum, &[2]))
+            .unwrap()
+            .footer(Aggregate::Sum, &[2]);
+        data.push(&["sda", "alpha", "10"]);
+        data.push(&["sda", "beta", "1"]);
+        data.push(&["sdb", "alpha", "20"]);
+
+        assert_eq!(
+            vec![
+                "┌─────┬────┬────┐",
+                "│host │disk│used│",
+                "├─────┼────┼────┤",
+                "│     │sda │ 10 │",
+                "│     ├────┼────┤",
+                "│alpha│sdb │ 20 │",
+                "│     ├────┼────┤",
+                "│     │sum │ 30 │",
+                "┝━━━━━┿━━━━┿━━━━┥",
+                "│     │sda │ 1  │",
+                "│beta ├────┼────┤",
+                "│     │sum │ 1  │",
+                "┝━━━━━┿━━━━┿━━━━┥",
+                "│ sum │    │ 31 │",
+                "└─────┴────┴────┘",
+            ],
+            Table::from(data).render()
+        );
+    }
+
+    #[test]
+    fn expanded() {
+        let mut data = DataTable::new(&["host", "used"]);
+        data.push(&["alpha", "10"]);
+        data.push(&["beta", "1"]);
+
+        assert_eq!(12, data.fit(20).size().pt_width);
+        assert_eq!(
+            vec![
+                "┌──────────────┐",
+                "│-[ RECORD 1 ]-│",
+                "├───────┬──────┤",
+                "│ host  │alpha │",
+                "├───────┼──────┤",
+                "│ used  │  10  │",
+                "├───────┴──────┤",
+                "│-[ RECORD 2 ]-│",
+                "├───────┬──────┤",
+                "│ host  │ beta │",
+                "├───────┼──────┤",
+                "│ used  │  1   │",
+                "└───────┴──────┘",
+            ],
+            data.fit(10).render()
+        );
+    }
+
+    #[test]
+    fn no_records() {
+        let data = DataTable::new(&["host", "used"]).footer(Aggregate::Sum, &[1]);
+        assert_eq!(
+            vec![
+                "┌────┬────┐",
+                "│host│used│",
+                "┝━━━━┿━━━━┥",
+                "│sum │    │",
+                "└────┴────┘",
+            ],
+            Table::from(&data).render()
+        );
+        assert_eq!(
+            vec!["┌────────┐", "│(0 rows)│", "└────────┘"],
+            Table::from(data.build_expanded()).render()
+        );
+    }
+
+    #[test]
+    fn group_by_unknown_column() {
+        let error = ColumnError {
+            column: 3,
+            columns: 3,
+        };
+        let data = DataTable::new(&["host", "cpu", "mem"]);
+        assert_eq!(Some(error), data.group_by(3).err());
+        let data = DataTable::new(&["host", "cpu", "mem"]);
+        let error = data.subtotal(Aggregate::Sum, &[1, 4]).err();
+        assert_eq!(Some(4), error.map(|e| e.column));
+    }
+
+    #[test]
+    fn styling_rules() {
+        let stripe = Style::new().bg(console::Color::Black);
+        let red = Style::new().fg(console::Color::Red);
+        let mut data = DataTable::new(&["host", "delta", "status"])
+            .stripe(stripe)
+            .highlight(1, |value| value.starts_with('-'), red);
+        data.push(&["alpha", "-3", "ok"]);
+        data.push(&["beta", "5", "failed"]);
+        data.push(&["gamma", "-1", "failed"]);
+        data.select(&[1, 2]).unwrap();
+        let data = data.emphasize(|record| record[1] == "failed", Style::new().bold());
+
+        let table = Table::from(&data);
+        let style = |row: usize, col: usize| table.get(&[row, col]).unwrap().get_config().style;
+        assert_eq!(red, style(1, 0));
+        assert_eq!(Style::default(), style(1, 1));
+        assert_eq!(stripe.bold(), style(2, 0));
+        assert_eq!(red.bold(), style(3, 0));
+        assert_eq!(Style::default(), style(0, 0));
+
+        // rules do not keep a data table on its thread
+        fn shared<T: Send + Sync>(_: &T) {}
+        shared(&data);
+    }
+}
@@ -0,0 +1,234 @@
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use crate::{
+    borders::Width,
+    cells::{Col, Role, Selector, Stylesheet, Table},
+    config::{Patch, UserProperties},
+};
+
+use super::{data_table::cell_properties, Aggregate, DataTable};
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Column {
+    name: String,
+    title: Option<String>,
+    // applied to the body cells of the column
+    #[serde(default)]
+    set: Patch,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Total {
+    aggregate: Aggregate,
+    columns: Vec<String>,
+}
+
+// Table skeleton loaded from a config file and filled with records later:
+//
+//     [body]
+//     alignment = "right"
+//
+//     [[column]]
+//     name = "host"
+//     set = { alignment = "left" }
+//
+//     [[column]]
+//     name = "cpu"
+//     title = "CPU, %"
+//
+//     [[total]]
+//     aggregate = "sum"
+//     columns = ["cpu"]
+//
+//     [[theme.rule]]
+//     select = { role = "header" }
+//     set = { border = "heavy" }
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "LayoutDef")]
+pub struct Layout {
+    columns: Vec<Column>,
+    header: Option<UserProperties>,
+    body: Option<UserProperties>,
+    footer: Option<UserProperties>,
+    totals: Vec<Total>,
+    footer_rule: Option<Width>,
+    theme: Stylesheet,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayoutDef {
+    #[serde(rename = "column")]
+    columns: Vec<Column>,
+    header: Option<UserProperties>,
+    body: Option<UserProperties>,
+    footer: Option<UserProperties>,
+    #[serde(default, rename = "total")]
+    totals: Vec<Total>,
+    footer_rule: Option<Width>,
+    #[serde(default)]
+    theme: Stylesheet,
+}
+
+// totals may only name the declared columns
+impl TryFrom<LayoutDef> for Layout {
+    type Error = String;
+
+    fn try_from(def: LayoutDef) -> Result<Self, Self::Error> {
+        for total in def.totals.iter() {
+            for name in total.columns.iter() {
+                if !def.columns.iter().any(|c| &c.name == name) {
+                    return Err(format!("total over unknown column `{}`", name));
+                }
+            }
+        }
+        Ok(Self {
+            columns: def.columns,
+            header: def.header,
+            body: def.body,
+            footer: def.footer,
+            totals: def.totals,
+            footer_rule: def.footer_rule,
+            theme: def.theme,
+        })
+    }
+}
+
+impl Layout {
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
+    pub fn data_table(&self) -> DataTable {
+        let titles: Vec<&str> = self
+            .columns
+            .iter()
+            .map(|c| c.title.as_deref().unwrap_or(&c.name))
+            .collect();
+        let mut data = DataTable::new(&titles)
+            .header_properties(self.header.unwrap_or_else(cell_properties))
+            .properties(self.body.unwrap_or_else(cell_properties))
+            .footer_properties(self.footer.unwrap_or_else(cell_properties));
+        for total in self.totals.iter() {
+            // the names were checked when the layout was loaded
+            let columns: Vec<usize> = total
+                .columns
+                .iter()
+                .filter_map(|name| self.column_index(name))
+                .collect();
+            data = data.footer(total.aggregate, &columns);
+        }
+        if let Some(rule) = self.footer_rule {
+            data = data.footer_rule(rule);
+        }
+        data
+    }
+
+    pub fn stylesheet(&self) -> Stylesheet {
+        let columns = self
+            .columns
+            .iter()
+            .enumerate()
+            .fold(Stylesheet::new(), |sheet, (i, column)| {
+                sheet.rule(Selector::new().role(Role::Body).column(i), column.set)
+            });
+        columns.merge(&self.theme)
+    }
+
+    // records hold values in the column order
+    pub fn fill<S: ToString>(&self, records: &[Vec<S>]) -> Table<Col> {
+        let mut data = self.data_table();
+        for record in records.iter() {
+            data.push(record);
+        }
+        let mut table = Table::from(data);
+        table.apply_stylesheet(&self.stylesheet());
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file;
+
+    const LAYOUT: &str = r#"
+        [body]
+        border = "light"
+        alignment = "right"
+
+        [[column]]
+        name = "host"
+        set = { alignment = "left" }
+
+        [[column]]
+        name = "cpu"
+        title = "CPU"
+
+        [[total]]
+        aggregate = "sum"
+        columns = ["cpu"]
+
+        [[theme.rule]]
+        select = { role = "header" }
+        set = { border = "heavy", style = { fg = "red", bold = true } }
+    "#;
+
+    #[test]
+    fn toml_layout() {
+        let layout: Layout = file::from_toml(LAYOUT).unwrap();
+        let table = layout.fill(&[vec!["alpha", "10"], vec!["beta", "200"]]);
+        assert_eq!(
+            vec![
+                "┏━━━━━┳━━━┓",
+                "┃host ┃CPU┃",
+                "┡━━━━━╇━━━┩",
+                "│alpha│ 10│",
+                "├─────┼───┤",
+                "│beta │200│",
+                "┝━━━━━┿━━━┥",
+                "│ sum │210│",
+                "└─────┴───┘",
+            ],
+            table
+                .render()
+                .iter()
+                .map(|line| console::strip_ansi_codes(line).to_string())
+                .collect::<Vec<_>>()
+        );
+        // colours may be on or off in the test run, so the style is checked on the cell
+        let header = table.get(&[0, 0]).unwrap().get_config().style;
+        assert_eq!(
+            crate::config::Style::new().fg(console::Color::Red).bold(),
+            header
+        );
+    }
+
+    #[test]
+    fn unknown_total_column() {
+        let layout = LAYOUT.replace(r#"columns = ["cpu"]"#, r#"columns = ["cpu", "mem"]"#);
+        let error = file::from_toml::<Layout>(&layout).unwrap_err();
+        assert!(error.to_string().contains("unknown column `mem`"));
+    }
+
+    #[test]
+    fn json_properties() {
+        let props: UserProperties =
+            file::from_json(r#"{"border": "block", "padding": 1, "span_width": 2}"#).unwrap();
+        assert_eq!(Width::Block, props.border);
+        assert_eq!((1, 2), (props.padding, props.span_width));
+
+        let error = file::from_json::<UserProperties>(r#"{"boder": "heavy"}"#);
+        assert!(error.is_err());
+        let error = file::from_toml::<Patch>("style = { fg = \"purple\" }");
+        assert!(error.unwrap_err().to_string().contains("purple"));
+    }
+}
@@ -0,0 +1,21 @@
+mod aggregate;
+pub use aggregate::Aggregate;
+
+mod sort;
+pub use sort::{Comparator, Order, SortKey};
+
+mod rule;
+
+mod data_table;
+pub use data_table::{ColumnError, DataTable};
+
+mod tree;
+pub use tree::{TreeNode, TreeTable};
+
+mod parse;
+pub use parse::{parse, ParseError};
+
+#[cfg(feature = "serde")]
+mod layout;
+#[cfg(feature = "serde")]
+pub use layout::Layout;
@@ -0,0 +1,854 @@
+use std::{fmt, ops::Range};
+
+use crate::{
+    borders::{Arms, Width},
+    cells::{CaptionPosition, Col, DrawCell, Row, Table, TextCell},
+    config::{Alignment, CellConfig, UserProperties},
+};
+
+use super::data_table::{cell_properties, tagged};
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    NoTable,
+    // 1-based line of the text where the cell starts
+    Irregular(usize),
+    Interlocked,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::NoTable => write!(f, "no table outline found"),
+            ParseError::Irregular(line) => {
+                write!(f, "cell starting on line {} is not a rectangle", line)
+            }
+            ParseError::Interlocked => write!(f, "cells cannot be split into rows and columns"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// second column of a wide char
+const TAIL: char = '\0';
+
+// ascii outlines are read as light lines, '=' as the heavy ones
+fn arms(ch: char) -> Option<Arms> {
+    let line = |horizontal: Width, vertical: Width| Arms {
+        up: vertical,
+        left: horizontal,
+        down: vertical,
+        right: horizontal,
+    };
+    match ch {
+        '-' => Some(line(Width::Light, Width::None)),
+        '=' | '═' => Some(line(Width::Heavy, Width::None)),
+        '|' => Some(line(Width::None, Width::Light)),
+        '║' => Some(line(Width::None, Width::Heavy)),
+        '+' => Some(line(Width::Light, Width::Light)),
+        ' ' | TAIL => None,
+        _ => Arms::from_char(ch),
+    }
+}
+
+fn is_horizontal(ch: char) -> bool {
+    // ':' marks the alignment on markdown separator lines
+    ch == ':' || arms(ch).is_some_and(|a| a.left != Width::None || a.right != Width::None)
+}
+
+fn is_vertical(ch: char) -> bool {
+    arms(ch).is_some_and(|a| a.up != Width::None || a.down != Width::None)
+}
+
+fn is_heavy(ch: char) -> bool {
+    arms(ch).is_some_and(|a| [a.left, a.right].contains(&Width::Heavy))
+}
+
+// A line made mostly of horizontal strokes, a title or a stray letter
+// on it is fine as long as the strokes outnumber it. A junction with a
+// stroke next to it starts a rule that stops under a tall cell.
+fn is_rule(line: &[char]) -> bool {
+    let strokes = line.iter().filter(|&&ch| is_horizontal(ch)).count();
+    let text = line
+        .iter()
+        .filter(|&&ch| ch != ' ' && ch != TAIL && arms(ch).is_none() && ch != ':')
+        .count();
+    // a frame corner to corner holds a title of any length
+    let mut drawn = line.iter().filter(|&&ch| ch != ' ' && ch != TAIL);
+    let framed = matches!(
+        (drawn.next(), drawn.next_back()),
+        (Some(&a), Some(&b)) if is_horizontal(a) && is_horizontal(b)
+    );
+    let junction = (0..line.len()).any(|x| {
+        let stroke = |x: usize| line.get(x).is_some_and(|&ch| is_horizontal(ch));
+        is_vertical(line[x])
+            && is_horizontal(line[x])
+            && ((x > 0 && stroke(x - 1)) || stroke(x + 1))
+    });
+    strokes > 0 && (strokes > text || framed && strokes > 1 || junction)
+}
+
+// result footers of psql and mysql: "(2 rows)", "2 rows in set (0.00 sec)", "Empty set (0.00 sec)"
+fn is_footer(line: &str) -> bool {
+    let count = |text: &str| {
+        let rows = text
+            .strip_suffix(" rows")
+            .or_else(|| text.strip_suffix(" row"));
+        rows.is_some_and(|n| !n.is_empty() && n.chars().all(|ch| ch.is_ascii_digit()))
+    };
+    let line = line.trim();
+    if let Some(rows) = line.strip_prefix('(').and_then(|l| l.strip_suffix(')')) {
+        return count(rows);
+    }
+    let timed = line
+        .strip_suffix(" sec)")
+        .and_then(|l| l.rsplit_once(" ("))
+        .filter(|(_, time)| time.parse::<f64>().is_ok());
+    match timed {
+        Some((set, _)) => set == "Empty set" || set.strip_suffix(" in set").is_some_and(count),
+        None => false,
+    }
+}
+
+fn is_markdown_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('|') && line.contains('-') && line.chars().all(|ch| "|-: ".contains(ch))
+}
+
+fn markdown_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line
+        .strip_suffix('|')
+        .filter(|l| !l.ends_with('\\'))
+        .unwrap_or(line);
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push(chars.next().unwrap())
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(ch),
+        }
+    }
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+// Markdown rows need not line up, so they are laid out again on the columns
+// of the widest cells. The colons of the separator give the column alignments.
+fn markdown(lines: &[String]) -> (Vec<String>, Vec<Alignment>) {
+    let rows: Vec<Vec<String>> = lines.iter().map(|line| markdown_cells(line)).collect();
+    let count = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![1; count];
+    let mut alignments = vec![Alignment::Default; count];
+    for (line, row) in lines.iter().zip(&rows) {
+        for (col, cell) in row.iter().enumerate() {
+            match is_markdown_rule(line) {
+                true => {
+                    alignments[col] = match (cell.starts_with(':'), cell.ends_with(':')) {
+                        (true, true) => Alignment::Center,
+                        (true, false) => Alignment::Left,
+                        (false, true) => Alignment::Right,
+                        (false, false) => Alignment::Default,
+                    }
+                }
+                false => widths[col] = widths[col].max(console::measure_text_width(cell)),
+            }
+        }
+    }
+
+    let lines = lines
+        .iter()
+        .zip(&rows)
+        .map(|(line, row)| {
+            let cells: Vec<String> = (0..count)
+                .map(|col| match is_markdown_rule(line) {
+                    true => "-".repeat(widths[col] + 2),
+                    false => {
+                        let cell = row.get(col).map_or("", String::as_str);
+                        let alignment = alignments[col].console(false);
+                        let cell = console::pad_str(cell, widths[col], alignment, None);
+                        format!(" {} ", cell)
+                    }
+                })
+                .collect();
+            format!("|{}|", cells.join("|"))
+        })
+        .collect();
+    (lines, alignments)
+}
+
+// chars of the line, wide ones followed by a tail so columns line up
+fn columns(line: &str) -> Vec<char> {
+    let mut chars = vec![];
+    let mut buffer = [0; 4];
+    for ch in line.chars() {
+        let width = match ch.is_ascii() {
+            true => 1,
+            false => console::measure_text_width(ch.encode_utf8(&mut buffer)),
+        };
+        if width == 0 {
+            continue;
+        }
+        chars.push(ch);
+        chars.extend(std::iter::repeat_n(TAIL, width - 1));
+    }
+    chars
+}
+
+// A '█' of a block outline reaches out to the neighbours that reach back
+// to it, so it reads as the heavy line of that shape.
+fn unblock(lines: &mut [Vec<char>]) {
+    let source = lines.to_vec();
+    let at = |y: Option<usize>, x: Option<usize>| {
+        let ch = *source.get(y?)?.get(x?)?;
+        arms(ch)
+    };
+    let reach = |arms: Option<Arms>, side: fn(&Arms) -> Width| {
+        if arms.is_some_and(|a| side(&a) != Width::None) {
+            Width::Heavy
+        } else {
+            Width::None
+        }
+    };
+    for (y, line) in lines.iter_mut().enumerate() {
+        for (x, ch) in line.iter_mut().enumerate() {
+            if *ch != '█' {
+                continue;
+            }
+            let up = reach(at(y.checked_sub(1), Some(x)), |a| a.down);
+            let left = reach(at(Some(y), x.checked_sub(1)), |a| a.right);
+            let down = reach(at(Some(y + 1), Some(x)), |a| a.up);
+            let right = reach(at(Some(y), Some(x + 1)), |a| a.left);
+            if [up, left, down, right] != [Width::None; 4] {
+                *ch = Width::draw_char(&up, &left, &down, &right);
+            }
+        }
+    }
+}
+
+// text over a rule, the junctions it was written around are left out
+fn label(line: &[char]) -> Option<String> {
+    let text: String = line
+        .iter()
+        .filter(|&&ch| ch != TAIL && arms(ch).is_none_or(|arms| arms.is_plain()))
+        .map(|&ch| if arms(ch).is_some() { ' ' } else { ch })
+        .collect();
+    let text = text.trim();
+    Some(text.to_string()).filter(|text| !text.is_empty())
+}
+
+// a leaf on the grid of bands and column slots
+struct Parsed {
+    rows: Range<usize>,
+    cols: Range<usize>,
+    text: String,
+    padding: Option<(usize, usize)>,
+}
+
+struct Outline {
+    // chars with a blank frame around, so virtual edges fall on it
+    chars: Vec<Vec<char>>,
+    // text lines of every band
+    bands: Vec<Range<usize>>,
+    // rules between a band and the next, empty when they are split virtually
+    rules: Vec<Vec<usize>>,
+    // x of the lines between the column slots
+    xs: Vec<usize>,
+}
+
+impl Outline {
+    fn new(lines: &[Vec<char>]) -> Result<Self, ParseError> {
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0) + 2;
+        let mut chars = vec![vec![' '; width]];
+        for line in lines {
+            let mut row = vec![' '];
+            row.extend_from_slice(line);
+            row.resize(width, ' ');
+            chars.push(row);
+        }
+        chars.push(vec![' '; width]);
+
+        let height = chars.len();
+        let rules: Vec<usize> = (1..height - 1).filter(|&y| is_rule(&chars[y])).collect();
+        if rules.is_empty() {
+            return Err(ParseError::NoTable);
+        }
+
+        // columns are where the rules are crossed
+        let mut xs: Vec<usize> = (0..width)
+            .filter(|&x| rules.iter().any(|&y| is_vertical(chars[y][x])))
+            .collect();
+        let last = (1..width - 1)
+            .rev()
+            .find(|&x| chars.iter().any(|row| row[x] != ' '))
+            .unwrap_or(0);
+        if xs.first().is_none_or(|&x| x > 1) {
+            xs.insert(0, 0);
+        }
+        if xs.last().is_some_and(|&x| x < last) {
+            xs.push(width - 1);
+        }
+
+        let mut bands: Vec<Range<usize>> = vec![];
+        let mut between: Vec<Vec<usize>> = vec![];
+        let mut pending = vec![];
+        for y in 1..height - 1 {
+            if rules.contains(&y) {
+                pending.push(y);
+                continue;
+            }
+            match bands.last_mut() {
+                Some(band) if pending.is_empty() => band.end = y + 1,
+                _ => {
+                    if !bands.is_empty() {
+                        between.push(std::mem::take(&mut pending));
+                    }
+                    pending.clear();
+                    bands.push(y..y + 1);
+                }
+            }
+        }
+        if bands.is_empty() {
+            return Err(ParseError::NoTable);
+        }
+
+        // Without rules between the records (psql, mysql, markdown) every line
+        // is a record. Otherwise lines between two rules belong to one cell.
+        let outline = match between.len() <= 1 {
+            true => {
+                let mut lines = vec![];
+                let mut rules = vec![];
+                for (i, band) in bands.iter().enumerate() {
+                    if i > 0 {
+                        rules.push(between[i - 1].clone());
+                    }
+                    for y in band.clone() {
+                        if y > band.start {
+                            rules.push(vec![]);
+                        }
+                        lines.push(y..y + 1);
+                    }
+                }
+                Self {
+                    chars,
+                    bands: lines,
+                    rules,
+                    xs,
+                }
+            }
+            false => Self {
+                chars,
+                bands,
+                rules: between,
+                xs,
+            },
+        };
+        Ok(outline)
+    }
+
+    // the line between the slot and the next one is drawn all along the band
+    fn vertical(&self, band: usize, col: usize) -> bool {
+        let x = self.xs[col + 1];
+        self.bands[band]
+            .clone()
+            .all(|y| is_vertical(self.chars[y][x]))
+    }
+
+    // a rule under the band is drawn over most of the slot
+    fn horizontal(&self, band: usize, col: usize) -> bool {
+        let slot = self.xs[col] + 1..self.xs[col + 1];
+        let rules = &self.rules[band];
+        rules.is_empty()
+            || rules.iter().any(|&y| {
+                let strokes = slot
+                    .clone()
+                    .filter(|&x| is_horizontal(self.chars[y][x]))
+                    .count();
+                strokes * 2 > slot.len()
+            })
+    }
+
+    fn heavy(&self, band: usize) -> bool {
+        self.rules[band]
+            .iter()
+            .any(|&y| self.chars[y].iter().any(|&ch| is_heavy(ch)))
+    }
+
+    fn cells(&self) -> Result<Vec<Parsed>, ParseError> {
+        let (height, width) = (self.bands.len(), self.xs.len() - 1);
+        let mut parent: Vec<usize> = (0..height * width).collect();
+        fn find(parent: &mut [usize], unit: usize) -> usize {
+            let mut root = unit;
+            while parent[root] != root {
+                root = parent[root];
+            }
+            parent[unit] = root;
+            root
+        }
+        let union = |parent: &mut [usize], a: usize, b: usize| {
+            let (a, b) = (find(parent, a), find(parent, b));
+            parent[a.max(b)] = a.min(b);
+        };
+        for band in 0..height {
+            for col in 0..width {
+                if col + 1 < width && !self.vertical(band, col) {
+                    union(&mut parent, band * width + col, band * width + col + 1);
+                }
+                if band + 1 < height && !self.horizontal(band, col) {
+                    union(&mut parent, band * width + col, (band + 1) * width + col);
+                }
+            }
+        }
+
+        // the units of every root must fill the box around them
+        let mut cells = vec![];
+        for unit in 0..height * width {
+            if find(&mut parent, unit) != unit {
+                continue;
+            }
+            let members: Vec<usize> = (unit..height * width)
+                .filter(|&other| find(&mut parent, other) == unit)
+                .collect();
+            let rows = unit / width..members.iter().map(|m| m / width).max().unwrap() + 1;
+            let cols = members.iter().map(|m| m % width).min().unwrap()
+                ..members.iter().map(|m| m % width).max().unwrap() + 1;
+            let line = self.bands[rows.start].start;
+            if members.len() != rows.len() * cols.len() || cols.start != unit % width {
+                return Err(ParseError::Irregular(line));
+            }
+            cells.push(self.read(rows, cols));
+        }
+        Ok(cells)
+    }
+
+    fn read(&self, rows: Range<usize>, cols: Range<usize>) -> Parsed {
+        let lines = self.bands[rows.start].start..self.bands[rows.end - 1].end;
+        let slot = self.xs[cols.start] + 1..self.xs[cols.end];
+        let lines: Vec<String> = lines
+            .map(|y| {
+                self.chars[y][slot.clone()]
+                    .iter()
+                    .filter(|&&ch| ch != TAIL)
+                    .collect()
+            })
+            .collect();
+
+        // spaces before and after the first line
+        let padding = lines
+            .iter()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| {
+                let left = line.len() - line.trim_start().len();
+                (left, line.len() - line.trim_end().len())
+            });
+        let text: Vec<&str> = lines
+            .iter()
+            .map(|line| line.trim())
+            .skip_while(|line| line.is_empty())
+            .collect();
+        let end = text
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |i| i + 1);
+
+        Parsed {
+            rows,
+            cols,
+            text: text[..end].join("\n"),
+            padding,
+        }
+    }
+}
+
+// cuts through the box that no cell goes across, none when it cannot be split that way
+fn cuts(cells: &[&Parsed], range: Range<usize>, horizontal: bool) -> Vec<usize> {
+    let span = |cell: &&Parsed| match horizontal {
+        true => cell.rows.clone(),
+        false => cell.cols.clone(),
+    };
+    (range.start + 1..range.end)
+        .filter(|&cut| {
+            cells
+                .iter()
+                .map(span)
+                .all(|span| cut <= span.start || cut >= span.end)
+        })
+        .collect()
+}
+
+fn pieces(range: &Range<usize>, cuts: &[usize]) -> Vec<Range<usize>> {
+    let mut bounds = vec![range.start];
+    bounds.extend_from_slice(cuts);
+    bounds.push(range.end);
+    bounds.windows(2).map(|w| w[0]..w[1]).collect()
+}
+
+// rows of the box, a single one when it has to be cut into columns first
+fn split(
+    cells: &[&Parsed],
+    rows: Range<usize>,
+    cols: Range<usize>,
+    columns: &[UserProperties],
+) -> Result<Vec<Box<dyn DrawCell>>, ParseError> {
+    let inside = |rows: &Range<usize>, cols: &Range<usize>| -> Vec<&Parsed> {
+        cells
+            .iter()
+            .filter(|cell| rows.contains(&cell.rows.start) && cols.contains(&cell.cols.start))
+            .copied()
+            .collect()
+    };
+    let cuts = cuts(cells, rows.clone(), true);
+    match cuts.is_empty() {
+        true => Ok(vec![build(cells, rows, cols, columns)?]),
+        false => pieces(&rows, &cuts)
+            .into_iter()
+            .map(|piece| build(&inside(&piece, &cols), piece, cols.clone(), columns))
+            .collect(),
+    }
+}
+
+// The text of a column is aligned to the side its cells all keep to within
+// the margin, the least padding of any cell. Headers are often centred over
+// the column, so they are left out.
+fn infer_alignments(cells: &[&Parsed], width: usize, header: bool) -> Vec<Alignment> {
+    let margin = cells
+        .iter()
+        .filter_map(|cell| cell.padding)
+        .map(|(left, right)| left.min(right))
+        .min()
+        .unwrap_or(0);
+    (0..width)
+        .map(|col| {
+            let mut sides = cells
+                .iter()
+                .filter(|cell| cell.cols == (col..col + 1) && !(header && cell.rows.start == 0))
+                .filter_map(|cell| match cell.padding? {
+                    // a cell filled up to the margin on both sides could be either
+                    (left, right) if left == margin && right == margin => None,
+                    (left, _) if left == margin => Some(Alignment::Left),
+                    (_, right) if right == margin => Some(Alignment::Right),
+                    _ => Some(Alignment::Center),
+                });
+            match sides.next() {
+                Some(side) if side != Alignment::Center && sides.all(|other| other == side) => side,
+                _ => Alignment::Default,
+            }
+        })
+        .collect()
+}
+
+// Guillotine cuts: rows are split off first, then columns, a cell that
+// spans several slots keeps them as its span.
+fn build(
+    cells: &[&Parsed],
+    rows: Range<usize>,
+    cols: Range<usize>,
+    columns: &[UserProperties],
+) -> Result<Box<dyn DrawCell>, ParseError> {
+    if let [cell] = cells {
+        // a cell over several columns keeps their alignment only when they agree
+        let spanned = &columns[cell.cols.clone()];
+        let alignment = match spanned[0].alignment {
+            first if spanned.iter().all(|column| column.alignment == first) => first,
+            _ => Alignment::Default,
+        };
+        let props = UserProperties {
+            alignment,
+            span_width: cell.cols.len(),
+            span_height: cell.rows.len(),
+            ..spanned[0]
+        };
+        return Ok(Box::new(TextCell::new(
+            cell.text.clone(),
+            CellConfig::from(props),
+        )));
+    }
+
+    if !cuts(cells, rows.clone(), true).is_empty() {
+        let rows = split(cells, rows, cols, columns)?;
+        return Ok(Box::new(Col::new(rows, CellConfig::default())));
+    }
+    let cuts = cuts(cells, cols.clone(), false);
+    if cuts.is_empty() {
+        return Err(ParseError::Interlocked);
+    }
+    let columns = pieces(&cols, &cuts)
+        .into_iter()
+        .map(|piece| {
+            let inside: Vec<&Parsed> = cells
+                .iter()
+                .filter(|cell| piece.contains(&cell.cols.start))
+                .copied()
+                .collect();
+            build(&inside, rows.clone(), piece, columns)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Box::new(Row::new(columns, CellConfig::default())))
+}
+
+// Reads a table printed by this crate, psql, mysql or as markdown back into
+// cells. Lines that stop inside a cell become spans, a title or caption on
+// the frame is kept. The first row is a header when a heavy rule or the only
+// rule of the table is under it. Cells are light, or block when the frame is
+// drawn with '█'. Every column gets one alignment, from the markdown separator
+// or from the padding of its cells, other styles of the source are not kept.
+pub fn parse(text: &str) -> Result<Table<Col>, ParseError> {
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            console::strip_ansi_codes(line)
+                .trim_end()
+                .replace('\t', " ")
+        })
+        .collect();
+    while lines
+        .last()
+        .is_some_and(|line| line.is_empty() || is_footer(line))
+    {
+        lines.pop();
+    }
+    let first = lines.iter().position(|line| !line.is_empty()).unwrap_or(0);
+    let mut lines = lines.split_off(first);
+    let mut given = None;
+    if lines.iter().any(|line| is_markdown_rule(line)) {
+        let (laid_out, alignments) = markdown(&lines);
+        lines = laid_out;
+        given = Some(alignments);
+    }
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut lines: Vec<Vec<char>> = lines
+        .iter()
+        .map(|line| columns(line.get(indent..).unwrap_or("")))
+        .collect();
+    // blocks have no heavier line, so a block outline has no heavy header rule
+    let blocks = lines
+        .iter()
+        .any(|line| line.iter().find(|&&ch| ch != ' ') == Some(&'█'));
+    let border = match blocks {
+        true => Width::Block,
+        false => Width::Light,
+    };
+    unblock(&mut lines);
+
+    // text under a closed frame is a caption
+    let mut below = None;
+    if lines.first().is_some_and(|line| is_rule(line)) {
+        if let Some(last) = lines.iter().rposition(|line| is_rule(line)) {
+            let caption: Vec<String> = lines[last + 1..]
+                .iter()
+                .filter_map(|line| label(line))
+                .collect();
+            below = Some(caption.join(" ")).filter(|caption| !caption.is_empty());
+            lines.truncate(last + 1);
+        }
+    }
+
+    let outline = Outline::new(&lines)?;
+    let cells = outline.cells()?;
+    let cells: Vec<&Parsed> = cells.iter().collect();
+    let (height, width) = (outline.bands.len(), outline.xs.len() - 1);
+    let under_header = !outline.rules.is_empty()
+        && !outline.rules[0].is_empty()
+        && (outline.heavy(0) && !blocks
+            || outline.rules.iter().filter(|r| !r.is_empty()).count() == 1);
+    let first_row = cells
+        .iter()
+        .all(|cell| cell.rows.start != 0 || cell.rows.end == 1);
+    let header = under_header && first_row;
+
+    let alignments = match given {
+        Some(alignments) if alignments.len() == width => alignments,
+        _ => infer_alignments(&cells, width, header),
+    };
+    let columns: Vec<UserProperties> = alignments
+        .into_iter()
+        .map(|alignment| UserProperties {
+            alignment,
+            border,
+            ..cell_properties()
+        })
+        .collect();
+    let mut rows = split(&cells, 0..height, 0..width, &columns)?;
+    if rows.len() > 1 && header {
+        let header = rows.remove(0);
+        rows.insert(0, tagged(header, &["header"]));
+    }
+
+    let mut table = Table::from(Col::new(rows, CellConfig::default()));
+    let (top, bottom) = (outline.bands[0].start, outline.bands[height - 1].end);
+    if let Some(title) = (1..top).find_map(|y| label(&outline.chars[y])) {
+        table = table.title(&title, Alignment::Default);
+    }
+    let caption = (bottom..outline.chars.len() - 1).find_map(|y| label(&outline.chars[y]));
+    if let Some(caption) = caption {
+        table = table.caption(&caption, Alignment::Default, CaptionPosition::Border);
+    } else if let Some(caption) = below {
+        table = table.caption(&caption, Alignment::Default, CaptionPosition::Below);
+    }
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str, props: UserProperties) -> Box<dyn DrawCell> {
+        Box::new(TextCell::new(text.to_string(), CellConfig::from(props)))
+    }
+
+    #[test]
+    fn round_trip() {
+        let light = cell_properties();
+        let tall = Col::new(
+            vec![
+                text("a", light),
+                text(
+                    "b",
+                    UserProperties {
+                        alignment: Alignment::Right,
+                        ..light
+                    },
+                ),
+            ],
+            CellConfig::default(),
+        );
+        let body = Row::new(
+            vec![
+                Box::new(tall),
+                text("50%", light),
+                text("x\nｗｉｄｅ", light),
+            ],
+            CellConfig::default(),
+        );
+        let total = text(
+            "total",
+            UserProperties {
+                span_width: 3,
+                ..light
+            },
+        );
+        let table = Table::from(Col::new(vec![Box::new(body), total], CellConfig::default()))
+            .title("Shares", Alignment::Default)
+            .caption("as of today", Alignment::Default, CaptionPosition::Below);
+
+        let rendered = table.render().join("\n");
+        assert_eq!(table.render(), parse(&rendered).unwrap().render());
+    }
+
+    #[test]
+    fn block_round_trip() {
+        let block = UserProperties {
+            border: Width::Block,
+            ..cell_properties()
+        };
+        let header = Row::new(
+            vec![text("id", block), text("name", block), text("city", block)],
+            CellConfig::default(),
+        );
+        let body = Row::new(
+            vec![
+                text("1", block),
+                text(
+                    "alice smith",
+                    UserProperties {
+                        span_width: 2,
+                        ..block
+                    },
+                ),
+            ],
+            CellConfig::default(),
+        );
+        let table = Table::from(Col::new(
+            vec![Box::new(header), Box::new(body)],
+            CellConfig::default(),
+        ))
+        .title("People", Alignment::Default);
+
+        let rendered = table.render().join("\n");
+        assert!(rendered.starts_with('█'));
+        assert_eq!(table.render(), parse(&rendered).unwrap().render());
+    }
+
+    #[test]
+    fn psql() {
+        let text = " id | name  | city\n\
+                    ----+-------+--------\n\
+                    \x20 1 | alice | Berlin\n\
+                    \x2012 | bob   |\n\
+                    (2 rows)\n";
+        assert_eq!(
+            vec![
+                "[cols=\"2,5,6\",options=\"header\"]",
+                "|===",
+                ">|id <|name ^|city",
+                ">|1 <|alice ^|Berlin",
+                ">|12 <|bob ^|",
+                "|===",
+            ],
+            parse(text).unwrap().render_asciidoc()
+        );
+    }
+
+    #[test]
+    fn mysql_and_markdown() {
+        let mysql = "+----+-------+\n\
+                     | id | name  |\n\
+                     +----+-------+\n\
+                     |  1 | alice |\n\
+                     | 12 | bob   |\n\
+                     +----+-------+\n\
+                     2 rows in set (0.00 sec)";
+        let markdown = "| id | name |\n|---:|:--|\n| 1 | alice |\n| 12 | bob |";
+        let mysql = parse(mysql).unwrap().render();
+        assert_eq!(
+            vec![
+                "┌──┬─────┐",
+                "│id│name │",
+                "├──┼─────┤",
+                "│ 1│alice│",
+                "├──┼─────┤",
+                "│12│bob  │",
+                "└──┴─────┘",
+            ],
+            mysql
+        );
+        assert_eq!(mysql, parse(markdown).unwrap().render());
+    }
+
+    #[test]
+    fn footers() {
+        assert!(is_footer("(1 row)"));
+        assert!(is_footer("12 rows in set (0.01 sec)"));
+        assert!(is_footer("Empty set (0.00 sec)"));
+        assert!(!is_footer("(see below)"));
+        assert!(!is_footer("| joined in set theory |"));
+        assert!(!is_footer("2 rows in set"));
+
+        let table = "+----+\n| id |\n+----+\n| 1  |\n+----+";
+        let empty = format!("{}\nEmpty set (0.00 sec)", table);
+        assert_eq!(
+            parse(table).unwrap().render(),
+            parse(&empty).unwrap().render()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Some(ParseError::NoTable), parse("no table here").err());
+        let irregular = "+---+---+\n\
+                         | a     |\n\
+                         +---+   +\n\
+                         | b | c |\n\
+                         +---+---+";
+        assert_eq!(Some(ParseError::Irregular(2)), parse(irregular).err());
+    }
+}
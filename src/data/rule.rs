@@ -0,0 +1,46 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::config::Style;
+
+// shared between the clones of a data table, which may go to other threads
+type RecordPredicate = Arc<dyn Fn(&[String]) -> bool + Send + Sync>;
+type ValuePredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+// Styling rules are evaluated when the table is built, so they follow
+// the records through sorting, filtering and grouping.
+#[derive(Clone)]
+pub(super) enum Rule {
+    Stripe(Style),
+    Row(RecordPredicate, Style),
+    Cell(usize, ValuePredicate, Style),
+}
+
+impl Rule {
+    // index is the position of the record among the displayed ones
+    pub(super) fn style(&self, index: usize, record: &[String], column: usize) -> Style {
+        match self {
+            Rule::Stripe(style) if index % 2 == 1 => *style,
+            Rule::Row(predicate, style) if predicate(record) => *style,
+            Rule::Cell(c, predicate, style) if *c == column && predicate(&record[column]) => *style,
+            _ => Style::default(),
+        }
+    }
+
+    pub(super) fn column_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Rule::Cell(column, _, _) => Some(column),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::Stripe(style) => write!(f, "Stripe({:?})", style),
+            Rule::Row(_, style) => write!(f, "Row({:?})", style),
+            Rule::Cell(column, _, style) => write!(f, "Cell({}, {:?})", column, style),
+        }
+    }
+}
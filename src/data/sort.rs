@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparator {
+    Lexicographic,
+    Numeric,
+    Natural,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SortKey {
+    pub column: usize,
+    pub comparator: Comparator,
+    pub order: Order,
+}
+
+impl SortKey {
+    pub fn new(column: usize, comparator: Comparator, order: Order) -> Self {
+        Self {
+            column,
+            comparator,
+            order,
+        }
+    }
+
+    pub fn compare(&self, a: &[String], b: &[String]) -> Ordering {
+        let ordering = self.comparator.compare(&a[self.column], &b[self.column]);
+        match self.order {
+            Order::Ascending => ordering,
+            Order::Descending => ordering.reverse(),
+        }
+    }
+}
+
+fn compare_numeric(a: &str, b: &str) -> Ordering {
+    // values that are not numbers go after the numbers, NaN after the other numbers
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(x), Ok(y)) => match (x.is_nan(), y.is_nan()) {
+            (false, false) => x.total_cmp(&y),
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+        },
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+fn chunks(text: &str) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    let mut digits = None;
+    for (i, ch) in text.char_indices() {
+        let is_digit = ch.is_ascii_digit();
+        if digits.is_some_and(|d| d != is_digit) {
+            chunks.push(&text[start..i]);
+            start = i;
+        }
+        digits = Some(is_digit);
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
+}
+
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    for (x, y) in chunks(a).into_iter().zip(chunks(b)) {
+        let both_digits = x.starts_with(|c: char| c.is_ascii_digit())
+            && y.starts_with(|c: char| c.is_ascii_digit());
+        let ordering = if both_digits {
+            let x = x.trim_start_matches('0');
+            let y = y.trim_start_matches('0');
+            x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+        } else {
+            x.cmp(y)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    chunks(a).len().cmp(&chunks(b).len()).then_with(|| a.cmp(b))
+}
+
+impl Comparator {
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Comparator::Lexicographic => a.cmp(b),
+            Comparator::Numeric => compare_numeric(a, b),
+            Comparator::Natural => compare_natural(a, b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(comparator: Comparator, values: &[&str]) -> Vec<String> {
+        let mut values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        values.sort_by(|a, b| comparator.compare(a, b));
+        values
+    }
+
+    #[test]
+    fn comparators() {
+        let values = ["host10", "host2", "host1", "n/a", "10", "9.5"];
+        assert_eq!(
+            vec!["10", "9.5", "host1", "host10", "host2", "n/a"],
+            sorted(Comparator::Lexicographic, &values)
+        );
+        assert_eq!(
+            vec!["9.5", "10", "host1", "host10", "host2", "n/a"],
+            sorted(Comparator::Numeric, &values)
+        );
+        assert_eq!(
+            vec!["9.5", "10", "host1", "host2", "host10", "n/a"],
+            sorted(Comparator::Natural, &values)
+        );
+        assert_eq!(
+            vec!["-1", "2", "inf", "NaN", "-nan", "x"],
+            sorted(Comparator::Numeric, &["NaN", "2", "x", "inf", "-nan", "-1"])
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use crate::{
+    borders::Width,
+    cells::{Col, DrawCell, Row, Table, TextCell},
+    config::{Alignment, CellConfig, UserProperties},
+};
+
+use super::data_table::{cell_properties, tagged, DataTable};
+
+#[derive(Clone, Debug)]
+pub struct TreeNode {
+    values: Vec<String>,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn new<S: ToString>(values: &[S]) -> Self {
+        Self {
+            values: values.iter().map(|v| v.to_string()).collect(),
+            children: vec![],
+        }
+    }
+
+    pub fn child(mut self, child: TreeNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn push(&mut self, child: TreeNode) {
+        self.children.push(child);
+    }
+
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    pub fn children(&self) -> &[TreeNode] {
+        &self.children
+    }
+
+    fn descendants(&self) -> usize {
+        self.children.iter().map(|c| 1 + c.descendants()).sum()
+    }
+}
+
+// prefixes drawn in front of a node label, in the order: branch, last branch, pipe, blank
+struct Guides([String; 4]);
+
+impl Guides {
+    fn new(width: Width) -> Self {
+        let w = match width {
+            Width::None => Width::Light,
+            // every block glyph is a full square, half blocks tell the last branch apart
+            Width::Block => {
+                return Self([
+                    "█▀".to_string(),
+                    "▀▀".to_string(),
+                    "█ ".to_string(),
+                    "  ".to_string(),
+                ])
+            }
+            width => width,
+        };
+        let n = Width::None;
+        let horizontal = Width::draw_char(&n, &w, &n, &w);
+        Self([
+            format!("{}{}", Width::draw_char(&w, &n, &w, &w), horizontal),
+            format!("{}{}", Width::draw_char(&w, &n, &n, &w), horizontal),
+            format!("{} ", Width::draw_char(&w, &n, &w, &n)),
+            "  ".to_string(),
+        ])
+    }
+}
+
+// The first column holds the node labels indented with tree guides,
+// the rest of the columns are aligned as in a regular data table.
+#[derive(Clone, Debug)]
+pub struct TreeTable {
+    headers: Vec<String>,
+    roots: Vec<TreeNode>,
+    depth: Option<usize>,
+
+    header_props: UserProperties,
+    props: UserProperties,
+}
+
+impl TreeTable {
+    pub fn new<S: ToString>(headers: &[S]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            roots: vec![],
+            depth: None,
+
+            header_props: cell_properties(),
+            props: cell_properties(),
+        }
+    }
+
+    pub fn push(&mut self, root: TreeNode) {
+        self.roots.push(root);
+    }
+
+    pub fn header_properties(mut self, props: UserProperties) -> Self {
+        self.header_props = props;
+        self
+    }
+
+    // guides are drawn with the border width of the body cells
+    pub fn properties(mut self, props: UserProperties) -> Self {
+        self.props = props;
+        self
+    }
+
+    // nodes deeper than depth are hidden, their count is shown next to the last visible parent
+    pub fn collapse(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn records(&self) -> Vec<Vec<String>> {
+        let guides = Guides::new(self.props.border);
+        let mut records = vec![];
+        for root in self.roots.iter() {
+            self.flatten(root, String::new(), String::new(), 0, &guides, &mut records);
+        }
+        records
+    }
+
+    fn flatten(
+        &self,
+        node: &TreeNode,
+        indent: String,
+        guide: String,
+        depth: usize,
+        guides: &Guides,
+        records: &mut Vec<Vec<String>>,
+    ) {
+        let [branch, last, pipe, blank] = &guides.0;
+        let collapsed = self.depth.is_some_and(|limit| depth >= limit);
+
+        let mut record = node.values.clone();
+        record.resize(self.headers.len().max(1), String::new());
+        record[0] = format!("{}{}{}", indent, guide, record[0]);
+        if collapsed && !node.children.is_empty() {
+            record[0] += &format!(" (+{})", node.descendants());
+        }
+        records.push(record);
+        if collapsed {
+            return;
+        }
+
+        // roots have no guide, so their children are not indented under it
+        let indent = match guide.as_str() {
+            "" => indent,
+            g if g == last => indent + blank,
+            _ => indent + pipe,
+        };
+        for (i, child) in node.children.iter().enumerate() {
+            let guide = if i + 1 == node.children.len() {
+                last
+            } else {
+                branch
+            };
+            self.flatten(
+                child,
+                indent.clone(),
+                guide.clone(),
+                depth + 1,
+                guides,
+                records,
+            );
+        }
+    }
+
+    pub fn build(&self) -> Col {
+        let label = UserProperties {
+            alignment: Alignment::Left,
+            ..self.props
+        };
+
+        let mut rows: Vec<Box<dyn DrawCell>> = vec![];
+        let header = DataTable::row(&self.headers, self.header_props);
+        rows.push(tagged(header, &["header"]));
+        for record in self.records() {
+            let cells = record
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let props = if i == 0 { label } else { self.props };
+                    let cell: Box<dyn DrawCell> =
+                        Box::new(TextCell::new(value, CellConfig::from(props)));
+                    cell
+                })
+                .collect();
+            rows.push(tagged(Row::new(cells, CellConfig::default()), &["body"]));
+        }
+
+        Col::new(rows, CellConfig::default())
+    }
+
+    pub fn print(&self) {
+        Table::from(self.build()).print();
+    }
+}
+
+impl From<&TreeTable> for Table<Col> {
+    fn from(tree: &TreeTable) -> Self {
+        Table::from(tree.build())
+    }
+}
+
+impl From<TreeTable> for Table<Col> {
+    fn from(tree: TreeTable) -> Self {
+        Table::from(tree.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> TreeTable {
+        let mut tree = TreeTable::new(&["name", "size"]);
+        tree.push(
+            TreeNode::new(&["src", "12"])
+                .child(
+                    TreeNode::new(&["cells", "8"])
+                        .child(TreeNode::new(&["row.rs", "3"]))
+                        .child(TreeNode::new(&["col.rs", "5"])),
+                )
+                .child(TreeNode::new(&["lib.rs", "4"])),
+        );
+        tree
+    }
+
+    #[test]
+    fn guides() {
+        let names: Vec<String> = tree().records().into_iter().map(|r| r[0].clone()).collect();
+        assert_eq!(
+            vec!["src", "├─cells", "│ ├─row.rs", "│ └─col.rs", "└─lib.rs"],
+            names
+        );
+
+        let heavy = tree().properties(UserProperties {
+            border: Width::Heavy,
+            ..UserProperties::default()
+        });
+        assert_eq!("┃ ┗━col.rs", heavy.records()[3][0]);
+
+        let block = tree().properties(UserProperties {
+            border: Width::Block,
+            ..UserProperties::default()
+        });
+        let names: Vec<String> = block.records().into_iter().map(|r| r[0].clone()).collect();
+        assert_eq!(
+            vec!["src", "█▀cells", "█ █▀row.rs", "█ ▀▀col.rs", "▀▀lib.rs"],
+            names
+        );
+    }
+
+    #[test]
+    fn collapse() {
+        let table = Table::from(tree().collapse(1));
+        assert_eq!(
+            vec![
+                "┌────────────┬────┐",
+                "│    name    │size│",
+                "├────────────┼────┤",
+                "│src         │ 12 │",
+                "├────────────┼────┤",
+                "│├─cells (+2)│ 8  │",
+                "├────────────┼────┤",
+                "│└─lib.rs    │ 4  │",
+                "└────────────┴────┘",
+            ],
+            table.render()
+        );
+    }
+}
@@ -1,3 +1,7 @@
 pub mod borders;
 pub mod cells;
 pub mod config;
+pub mod data;
+pub mod live;
+#[cfg(feature = "viewer")]
+pub mod viewer;
@@ -0,0 +1,208 @@
+use std::io;
+
+use console::Term;
+
+use crate::cells::{DrawCell, Table};
+
+// Keeps the last render on screen and redraws it in place on every update
+pub struct LiveTable {
+    term: Term,
+    lines: Vec<String>,
+    width: u16,
+}
+
+// how many terminal rows the line occupies once the terminal wraps it
+fn rows_taken(line: &str, width: u16) -> usize {
+    let width = (width as usize).max(1);
+    let length = console::measure_text_width(line);
+    1.max(length.div_ceil(width))
+}
+
+impl LiveTable {
+    pub fn new() -> Self {
+        Self::with_term(Term::stdout())
+    }
+
+    pub fn with_term(term: Term) -> Self {
+        let width = term.size().1;
+        Self {
+            term,
+            lines: vec![],
+            width,
+        }
+    }
+
+    pub fn update<T: DrawCell>(&mut self, table: &Table<T>) -> io::Result<()> {
+        self.update_lines(table.render())
+    }
+
+    pub fn update_lines(&mut self, lines: Vec<String>) -> io::Result<()> {
+        let width = self.term.size().1;
+        self.term.hide_cursor()?;
+
+        for step in redraw(&self.lines, &lines, width, width != self.width) {
+            match step {
+                Step::Up(rows) => self.term.move_cursor_up(rows)?,
+                Step::Keep(rows) => self.term.move_cursor_down(rows)?,
+                Step::Write(line) => {
+                    self.term.clear_line()?;
+                    self.term.write_line(line)?;
+                }
+                Step::ClearBelow => self.term.clear_to_end_of_screen()?,
+            }
+        }
+
+        self.term.flush()?;
+        self.lines = lines;
+        self.width = width;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Step<'a> {
+    Up(usize),
+    // the line on screen is still right, the cursor just goes past its rows
+    Keep(usize),
+    Write(&'a str),
+    ClearBelow,
+}
+
+// What turns the old render on screen into the new one, width is the one of
+// the terminal now. A line is only rewritten in place while it and the old one
+// take a single row, otherwise the rows below move and are all written again.
+fn redraw<'a>(old: &[String], new: &'a [String], width: u16, resized: bool) -> Vec<Step<'a>> {
+    let mut steps = vec![];
+    // the old render may have been rewrapped, so its rows are counted at the new width
+    let rows = old.iter().map(|line| rows_taken(line, width)).sum();
+    steps.push(Step::Up(rows));
+    if resized {
+        steps.push(Step::ClearBelow);
+        steps.extend(new.iter().map(|line| Step::Write(line)));
+        return steps;
+    }
+
+    for (i, line) in new.iter().enumerate() {
+        let taken = rows_taken(line, width);
+        match old.get(i) {
+            Some(old) if old == line => steps.push(Step::Keep(taken)),
+            Some(old) if taken == 1 && rows_taken(old, width) == 1 => steps.push(Step::Write(line)),
+            // past the old render there is nothing left to clear
+            None => steps.push(Step::Write(line)),
+            Some(_) => {
+                steps.push(Step::ClearBelow);
+                steps.extend(new[i..].iter().map(|line| Step::Write(line)));
+                return steps;
+            }
+        }
+    }
+    if new.len() < old.len() {
+        steps.push(Step::ClearBelow);
+    }
+    steps
+}
+
+impl Default for LiveTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for LiveTable {
+    fn drop(&mut self) {
+        // the cursor is already below the last render, which stays on screen as is
+        let _ = self.term.show_cursor();
+        let _ = self.term.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn only_changed_lines() {
+        let old = lines(&["┌─┬─┐", "│1│2│", "└─┴─┘"]);
+        let new = lines(&["┌─┬─┐", "│1│3│", "└─┴─┘"]);
+        assert_eq!(
+            vec![
+                Step::Up(3),
+                Step::Keep(1),
+                Step::Write("│1│3│"),
+                Step::Keep(1)
+            ],
+            redraw(&old, &new, 80, false)
+        );
+
+        // the first update writes everything
+        let first = lines(&["┌─┐", "└─┘"]);
+        assert_eq!(
+            vec![Step::Up(0), Step::Write("┌─┐"), Step::Write("└─┘")],
+            redraw(&[], &first, 80, false)
+        );
+    }
+
+    #[test]
+    fn shrinking_and_resizing() {
+        let old = lines(&["┌─┐", "│1│", "├─┤", "│2│", "└─┘"]);
+        let new = lines(&["┌─┐", "│1│", "└─┘"]);
+        assert_eq!(
+            vec![
+                Step::Up(5),
+                Step::Keep(1),
+                Step::Keep(1),
+                Step::Write("└─┘"),
+                Step::ClearBelow
+            ],
+            redraw(&old, &new, 80, false)
+        );
+
+        // every line of the old render took two rows of the narrower terminal
+        let old = lines(&["┌────┐", "└────┘"]);
+        assert_eq!(
+            vec![
+                Step::Up(4),
+                Step::ClearBelow,
+                Step::Write("┌─┐"),
+                Step::Write("│1│"),
+                Step::Write("└─┘")
+            ],
+            redraw(&old, &new, 4, true)
+        );
+        assert_eq!(2, rows_taken("\x1b[31m┌────┐\x1b[0m", 4));
+    }
+
+    #[test]
+    fn lines_longer_than_the_terminal() {
+        // the middle line wraps over two rows of a terminal 6 columns wide
+        let old = lines(&["┌────────┐", "│1234567890│", "└────────┘"]);
+        let new = lines(&["┌────────┐", "│1234567890│", "└────────┘", "done"]);
+        assert_eq!(
+            vec![
+                Step::Up(6),
+                Step::Keep(2),
+                Step::Keep(2),
+                Step::Keep(2),
+                Step::Write("done")
+            ],
+            redraw(&old, &new, 6, false)
+        );
+
+        // once a wrapped line changes, the rows below it are written again
+        let new = lines(&["┌────────┐", "│12│", "└────────┘"]);
+        assert_eq!(
+            vec![
+                Step::Up(6),
+                Step::Keep(2),
+                Step::ClearBelow,
+                Step::Write("│12│"),
+                Step::Write("└────────┘")
+            ],
+            redraw(&old, &new, 6, false)
+        );
+    }
+}
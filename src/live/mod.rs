@@ -0,0 +1,2 @@
+mod live_table;
+pub use live_table::LiveTable;
@@ -0,0 +1,2 @@
+mod table_viewer;
+pub use table_viewer::{Action, TableViewer};
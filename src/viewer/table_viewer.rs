@@ -0,0 +1,362 @@
+use std::io;
+
+use console::{Key, Term};
+
+use crate::cells::{DrawCell, Table};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Continue,
+    Search,
+    Quit,
+}
+
+// Full-screen pager over a table: the header row and the first column stay in place,
+// everything else scrolls. Column widths are changed right in the table grid.
+pub struct TableViewer<'a, T: DrawCell> {
+    table: &'a mut Table<T>,
+    lines: Vec<String>,
+    top: usize,
+    left: usize,
+    column: usize,
+    search: Option<String>,
+    height: usize,
+    width: usize,
+}
+
+impl<'a, T: DrawCell> TableViewer<'a, T> {
+    pub fn new(table: &'a mut Table<T>) -> Self {
+        let mut viewer = Self {
+            table,
+            lines: vec![],
+            top: 0,
+            left: 0,
+            column: 0,
+            search: None,
+            height: 24,
+            width: 80,
+        };
+        viewer.refresh();
+        viewer
+    }
+
+    fn refresh(&mut self) {
+        self.lines = self
+            .table
+            .render()
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect();
+        self.scroll(0, 0);
+    }
+
+    // top border, the first grid row and the separator under it
+    // the header may span several grid rows, the tallest cell of the first row decides
+    fn frozen_rows(&self) -> usize {
+        let heights = self.table.row_heights();
+        let columns = self.table.column_widths().len();
+        let mut span = 0;
+        let mut col = 0;
+        while let Some(cell) = self.table.get_at(0, col) {
+            let config = cell.get_config();
+            span = span.max(config.span_height);
+            col += config.span_width.max(1);
+            if col >= columns {
+                break;
+            }
+        }
+        let header: usize = heights.iter().take(span.max(1)).sum::<usize>() + 1;
+        header.min(self.lines.len())
+    }
+
+    fn frozen_cols(&self) -> usize {
+        let span = self
+            .table
+            .get_at(0, 0)
+            .map_or(1, |cell| cell.get_config().span_width);
+        self.table.column_widths().iter().take(span).sum::<usize>() + 1
+    }
+
+    fn body_height(&self) -> usize {
+        self.height.saturating_sub(1 + self.frozen_rows())
+    }
+
+    fn scroll(&mut self, down: isize, right: isize) {
+        let max_top = self
+            .lines
+            .len()
+            .saturating_sub(self.frozen_rows() + self.body_height());
+        let line_width = self
+            .lines
+            .first()
+            .map_or(0, |line| console::measure_text_width(line));
+        let max_left = line_width.saturating_sub(self.width.max(self.frozen_cols()));
+
+        let shift = |value: usize, by: isize, max: usize| -> usize {
+            let value = if by < 0 {
+                value.saturating_sub(by.unsigned_abs())
+            } else {
+                value + by as usize
+            };
+            value.min(max)
+        };
+        self.top = shift(self.top, down, max_top);
+        self.left = shift(self.left, right, max_left);
+    }
+
+    fn resize(&mut self, delta: isize) {
+        let width = self.table.column_widths()[self.column] as isize + delta;
+        if !self.table.resize_column(self.column, width.max(0) as usize) {
+            return;
+        }
+        self.refresh();
+    }
+
+    pub fn set_size(&mut self, height: usize, width: usize) {
+        self.height = height;
+        self.width = width;
+        self.scroll(0, 0);
+    }
+
+    pub fn find(&mut self, pattern: &str) {
+        self.search = Some(pattern.to_string());
+        self.find_next();
+    }
+
+    fn find_next(&mut self) {
+        let pattern = match &self.search {
+            Some(pattern) if !pattern.is_empty() => pattern.clone(),
+            _ => return,
+        };
+        let frozen = self.frozen_rows();
+        let position = |line: &str| {
+            line.find(&pattern)
+                .map(|at| console::measure_text_width(&line[..at]))
+        };
+
+        let count = self.lines.len() - frozen;
+        for offset in 1..=count {
+            let index = frozen + (self.top + offset) % count;
+            if let Some(x) = position(&self.lines[index]) {
+                self.top = index - frozen;
+                self.left = x.saturating_sub(self.frozen_cols());
+                self.scroll(0, 0);
+                return;
+            }
+        }
+    }
+
+    pub fn handle(&mut self, key: Key) -> Action {
+        let page = self.body_height().max(1) as isize;
+        let columns = self.table.column_widths().len();
+        match key {
+            Key::ArrowUp | Key::Char('k') => self.scroll(-1, 0),
+            Key::ArrowDown | Key::Char('j') => self.scroll(1, 0),
+            Key::ArrowLeft | Key::Char('h') => self.scroll(0, -1),
+            Key::ArrowRight | Key::Char('l') => self.scroll(0, 1),
+            Key::PageUp => self.scroll(-page, 0),
+            Key::PageDown | Key::Char(' ') => self.scroll(page, 0),
+            Key::Home => self.scroll(isize::MIN / 2, isize::MIN / 2),
+            Key::End => self.scroll(isize::MAX / 2, 0),
+            Key::Tab => self.column = (self.column + 1) % columns,
+            Key::BackTab => self.column = (self.column + columns - 1) % columns,
+            Key::Char('+') | Key::Char('>') => self.resize(1),
+            Key::Char('-') | Key::Char('<') => self.resize(-1),
+            Key::Char('n') => self.find_next(),
+            Key::Char('/') => return Action::Search,
+            Key::Char('q') | Key::Escape => return Action::Quit,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    pub fn frame(&self) -> Vec<String> {
+        let frozen_rows = self.frozen_rows();
+        let frozen_cols = self.frozen_cols().min(self.width);
+        let body = frozen_rows..self.lines.len();
+
+        let visible = self.lines[..frozen_rows].iter().chain(
+            self.lines[body]
+                .iter()
+                .skip(self.top)
+                .take(self.body_height()),
+        );
+        let mut frame: Vec<String> = visible
+            .map(|line| {
+                let tail = frozen_cols + self.left;
+                columns(line, 0, frozen_cols) + &columns(line, tail, self.left + self.width)
+            })
+            .collect();
+        frame.resize(self.height.saturating_sub(1), String::new());
+
+        let status = format!(
+            "line {}/{}  column {} width {}{}",
+            self.top + 1,
+            self.lines.len() - frozen_rows,
+            self.column + 1,
+            self.table.column_widths()[self.column],
+            match &self.search {
+                Some(pattern) => format!("  /{}", pattern),
+                None => String::new(),
+            }
+        );
+        frame.push(columns(&status, 0, self.width));
+        frame
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        let term = Term::stdout();
+        term.hide_cursor()?;
+        term.clear_screen()?;
+
+        let result = self.event_loop(&term);
+
+        term.clear_screen()?;
+        term.show_cursor()?;
+        result
+    }
+
+    fn event_loop(&mut self, term: &Term) -> io::Result<()> {
+        loop {
+            let (height, width) = term.size();
+            self.set_size(height as usize, width as usize);
+
+            term.move_cursor_to(0, 0)?;
+            for (i, line) in self.frame().iter().enumerate() {
+                term.clear_line()?;
+                if i + 1 < self.height {
+                    term.write_line(line)?;
+                } else {
+                    term.write_str(line)?;
+                }
+            }
+            term.flush()?;
+
+            match self.handle(term.read_key()?) {
+                Action::Continue => {}
+                Action::Quit => return Ok(()),
+                Action::Search => {
+                    term.clear_line()?;
+                    term.write_str("/")?;
+                    term.show_cursor()?;
+                    let pattern = term.read_line()?;
+                    term.hide_cursor()?;
+                    self.find(&pattern);
+                }
+            }
+        }
+    }
+}
+
+// the part of the line between two display columns, a wide char cut by either
+// edge is replaced with spaces
+fn columns(line: &str, from: usize, to: usize) -> String {
+    let mut part = String::new();
+    let mut x = 0;
+    let mut kept = false;
+    let mut buffer = [0; 4];
+    for ch in line.chars() {
+        let width = console::measure_text_width(ch.encode_utf8(&mut buffer));
+        if width == 0 {
+            // zero width chars go with the char before them
+            if kept {
+                part.push(ch);
+            }
+            continue;
+        }
+        kept = x >= from && x + width <= to;
+        if kept {
+            part.push(ch);
+        } else {
+            let cut = x.max(from)..(x + width).min(to);
+            part.extend(std::iter::repeat_n(' ', cut.len()));
+        }
+        x += width;
+    }
+    part
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::borders::Width;
+    use crate::cells::{Col, Row, TextCell};
+    use crate::config::{CellConfig, UserProperties};
+    use crate::data::DataTable;
+
+    #[test]
+    fn frozen_header_and_column() {
+        let mut data = DataTable::new(&["id", "name"]);
+        for i in 0..10 {
+            data.push(&[format!("{}", i), format!("item{}", i)]);
+        }
+        let mut table = Table::from(data);
+        let mut viewer = TableViewer::new(&mut table);
+        viewer.set_size(6, 6);
+
+        assert_eq!(
+            vec!["┌──┬──", "│id│na", "├──┼──", "│0 │it", "├──┼──", "line 1"],
+            viewer.frame()
+        );
+
+        viewer.handle(Key::ArrowDown);
+        viewer.handle(Key::ArrowDown);
+        viewer.handle(Key::ArrowRight);
+        assert_eq!(
+            vec!["┌──┬──", "│id│am", "├──┼──", "│1 │te", "├──┼──", "line 3"],
+            viewer.frame()
+        );
+
+        viewer.find("item7");
+        assert_eq!("│7 │it", viewer.frame()[3]);
+
+        viewer.handle(Key::Char('-'));
+        assert_eq!(2, viewer.table.column_widths()[0]);
+    }
+
+    #[test]
+    fn header_spanning_grid_rows() {
+        let light = || {
+            CellConfig::from(UserProperties {
+                border: Width::Light,
+                ..UserProperties::default()
+            })
+        };
+        let text =
+            |s: &str| -> Box<dyn DrawCell> { Box::new(TextCell::new(s.to_string(), light())) };
+        let row = |cols: Vec<Box<dyn DrawCell>>| -> Box<dyn DrawCell> {
+            Box::new(Row::new(cols, light()))
+        };
+
+        let name = Box::new(Col::new(vec![text("first"), text("name")], light()));
+        let mut rows = vec![row(vec![text("id"), name])];
+        for i in 0..10 {
+            rows.push(row(vec![text(&i.to_string()), text("item")]));
+        }
+        let mut table = Table::from(Col::new(rows, light()));
+        let mut viewer = TableViewer::new(&mut table);
+        viewer.set_size(8, 20);
+
+        viewer.handle(Key::ArrowDown);
+        let frame = viewer.frame();
+        assert_eq!("│  │first│", frame[1]);
+        assert_eq!("│id├─────┤", frame[2]);
+        assert_eq!("│  │name │", frame[3]);
+        assert_eq!("│1 │item │", frame[6]);
+    }
+
+    #[test]
+    fn wide_chars_scroll_by_columns() {
+        let mut data = DataTable::new(&["id", "city name"]);
+        data.push(&["1", "東京都"]);
+        let mut table = Table::from(data);
+        let mut viewer = TableViewer::new(&mut table);
+        viewer.set_size(6, 8);
+
+        assert_eq!("│1 │ 東 ", viewer.frame()[3]);
+        viewer.handle(Key::ArrowRight);
+        assert_eq!("│1 │東京", viewer.frame()[3]);
+        viewer.handle(Key::ArrowRight);
+        assert_eq!("│1 │ 京 ", viewer.frame()[3]);
+    }
+}